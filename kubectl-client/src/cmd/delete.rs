@@ -8,13 +8,14 @@ use kube::{
 use mlua::{Either, Error as LuaError, Lua, Result as LuaResult};
 
 use super::utils::dynamic_api;
-use crate::{structs::CmdDeleteArgs, with_client};
+use crate::{structs::CmdDeleteArgs, with_context_client};
 
 #[tracing::instrument]
 pub async fn delete_async(_lua: Lua, json: String) -> LuaResult<String> {
     let args: CmdDeleteArgs = serde_json::from_str(&json).unwrap();
 
-    with_client(move |client| async move {
+    let context = args.context.clone();
+    with_context_client(context.as_deref(), move |_, client| async move {
         let gvk = GroupVersionKind::gvk(&args.gvk.g, &args.gvk.v, &args.gvk.k);
         let (ar, caps) = discovery::pinned_kind(&client, &gvk)
             .await
@@ -6,7 +6,7 @@ use mlua::prelude::*;
 use mlua::Result as LuaResult;
 
 use crate::structs::CmdEditArgs;
-use crate::with_client;
+use crate::with_context_client;
 
 const FIELD_MANAGER: &str = "kubectl-edit-lua";
 
@@ -15,7 +15,8 @@ pub async fn edit_async(_lua: Lua, json: String) -> LuaResult<String> {
     let args: CmdEditArgs =
         serde_json::from_str(&json).map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
 
-    with_client(move |client| async move {
+    let context = args.context.clone();
+    with_context_client(context.as_deref(), move |_, client| async move {
         let edited_raw = std::fs::read_to_string(&args.path)
             .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
         let edited_yaml: serde_yaml::Value = serde_yaml::from_str(&edited_raw)
@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet};
 use http::Uri;
 use k8s_openapi::serde_json::{self};
 use kube::{
-    api::{ApiResource, DynamicObject, ListParams, ResourceExt, TypeMeta},
+    api::{ApiResource, DynamicObject, ResourceExt, TypeMeta},
     core::GroupVersionKind,
     discovery::{ApiCapabilities, Discovery, Scope},
     Client, Error,
@@ -17,9 +17,9 @@ use tracing::{trace_span, warn, Instrument};
 
 use super::utils::dynamic_api;
 use crate::{
-    store,
+    store::{self, WatchSelector},
     structs::{GetServerRawArgs, GetSingleArgs},
-    with_client,
+    with_client, with_context_client,
 };
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -62,13 +62,14 @@ pub async fn get_resources_async(
     group: String,
     version: String,
     namespace: Option<String>,
+    selector: &WatchSelector,
 ) -> Result<Vec<DynamicObject>, Error> {
     let gvk = GroupVersionKind::gvk(&group, &version, &kind);
     let (ar, caps) = kube::discovery::pinned_kind(client, &gvk).await?;
     let ar_api_version = ar.api_version.clone();
     let ar_kind = ar.kind.clone();
     let api = dynamic_api(ar, caps, client.clone(), namespace.as_deref(), true);
-    let mut list = api.list(&ListParams::default()).await?;
+    let mut list = api.list(&selector.list_params()).await?;
 
     for obj in &mut list.items {
         obj.managed_fields_mut().clear();
@@ -123,8 +124,11 @@ pub fn get_single(lua: &Lua, json: String) -> LuaResult<String> {
         .map(OutputMode::from_str)
         .unwrap_or_default();
 
-    with_client(move |client| async move {
-        if let Some(found) = store::get_single(&args.gvk.k, args.namespace.clone(), &args.name)? {
+    let context = args.context.clone();
+    with_context_client(context.as_deref(), move |context, client| async move {
+        if let Some(found) =
+            store::get_single_in_context(&context, &args.gvk.k, args.namespace.clone(), &args.name)?
+        {
             return Ok(output_mode.format(found));
         }
         get_resource_async(
@@ -151,11 +155,15 @@ pub async fn get_single_async(_lua: Lua, json: String) -> LuaResult<String> {
         .map(OutputMode::from_str)
         .unwrap_or_default();
 
-    with_client(move |client| async move {
+    let context = args.context.clone();
+    with_context_client(context.as_deref(), move |context, client| async move {
         if args.cached.unwrap_or(true) {
-            if let Some(found) =
-                store::get_single(&args.gvk.k, args.namespace.clone(), &args.name)?
-            {
+            if let Some(found) = store::get_single_in_context(
+                &context,
+                &args.gvk.k,
+                args.namespace.clone(),
+                &args.name,
+            )? {
                 return Ok(output_mode.format(found));
             }
         }
@@ -0,0 +1,364 @@
+use futures::{AsyncBufReadExt, TryStreamExt};
+use jiff::Timestamp;
+use k8s_openapi::api::core::v1::{ContainerState, ContainerStatus};
+use k8s_openapi::serde_json;
+use kube::api::LogParams;
+use serde::Serialize;
+use std::sync::Arc;
+
+use super::log_session::{
+    build_log_line, list_selected_pods, parse_duration, resolve_log_targets, LineOptions,
+    ResolvedContainer,
+};
+use crate::structs::LogConfig;
+use crate::with_client;
+
+/// Lines read from the previous instance when the config sets no `tail_lines`
+const PREVIOUS_TAIL_LINES: i64 = 500;
+
+/// Why the previous instance of a container ended, from `last_state.terminated`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Termination {
+    reason: Option<String>,
+    exit_code: i32,
+    signal: Option<i32>,
+    message: Option<String>,
+    started_at: Option<String>,
+    finished_at: Option<String>,
+}
+
+impl Termination {
+    fn from_status(status: &ContainerStatus) -> Option<Self> {
+        let t = status.last_state.as_ref()?.terminated.as_ref()?;
+        Some(Self {
+            reason: t.reason.clone(),
+            exit_code: t.exit_code,
+            signal: t.signal,
+            message: t.message.clone(),
+            started_at: t.started_at.as_ref().map(|ts| ts.0.to_string()),
+            finished_at: t.finished_at.as_ref().map(|ts| ts.0.to_string()),
+        })
+    }
+
+    /// `OOMKilled, exit code 137 at 2024-01-15T10:30:45Z: message`
+    fn describe(&self) -> String {
+        let mut out = format!(
+            "{}, exit code {}",
+            self.reason.as_deref().unwrap_or("Unknown"),
+            self.exit_code
+        );
+        if let Some(signal) = self.signal {
+            out.push_str(&format!(" (signal {signal})"));
+        }
+        if let Some(finished) = &self.finished_at {
+            out.push_str(&format!(" at {finished}"));
+        }
+        if let Some(message) = self.message.as_deref().and_then(|m| m.lines().next()) {
+            out.push_str(&format!(": {message}"));
+        }
+        out
+    }
+}
+
+/// Logs of one container instance, or why they could not be read.
+type InstanceLogs = Result<Vec<String>, String>;
+
+/// Previous and current logs of a container together with why it last restarted.
+#[derive(Debug, Serialize)]
+struct CrashContext {
+    pod: String,
+    container: String,
+    restart_count: i32,
+    /// Current state, e.g. `waiting: CrashLoopBackOff`
+    state: Option<String>,
+    termination: Option<Termination>,
+    /// `None` when the container has not restarted
+    previous: Option<InstanceLogs>,
+    current: InstanceLogs,
+}
+
+/// Row of a rendered line that gets a highlight in Lua.
+#[derive(Debug, PartialEq, Serialize)]
+struct CrashMark {
+    /// 0-based line index
+    row: usize,
+    /// `container`, `termination` or `section`
+    kind: &'static str,
+}
+
+fn describe_state(state: &ContainerState) -> Option<String> {
+    if let Some(waiting) = &state.waiting {
+        return Some(format!("waiting: {}", waiting.reason.as_deref().unwrap_or("Unknown")));
+    }
+    if let Some(terminated) = &state.terminated {
+        let reason = terminated.reason.as_deref().unwrap_or("Unknown");
+        return Some(format!("terminated: {reason}, exit code {}", terminated.exit_code));
+    }
+    state.running.as_ref().map(|_| "running".to_string())
+}
+
+/// `1 line`, `2 lines`
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("{n} {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+/// Lay the containers out as one view: a header per container with its state
+/// and last termination, then the previous and current logs.
+fn render(contexts: &[CrashContext]) -> (Vec<String>, Vec<CrashMark>) {
+    let mut lines = Vec::new();
+    let mut marks = Vec::new();
+    let mut push = |line: String, kind: Option<&'static str>| {
+        if let Some(kind) = kind {
+            marks.push(CrashMark {
+                row: lines.len(),
+                kind,
+            });
+        }
+        lines.push(line);
+    };
+
+    for (i, ctx) in contexts.iter().enumerate() {
+        if i > 0 {
+            push(String::new(), None);
+        }
+        let state = ctx.state.as_deref().unwrap_or("unknown");
+        push(
+            format!(
+                "== {}/{}: {state}, {}",
+                ctx.pod,
+                ctx.container,
+                count(ctx.restart_count.max(0) as usize, "restart")
+            ),
+            Some("container"),
+        );
+        if let Some(termination) = &ctx.termination {
+            push(format!("== last exit: {}", termination.describe()), Some("termination"));
+        }
+
+        let sections = [("previous", ctx.previous.as_ref()), ("current", Some(&ctx.current))];
+        for (name, logs) in sections {
+            match logs {
+                Some(Ok(logs)) => {
+                    push(
+                        format!("-- {name}: {}", count(logs.len(), "line")),
+                        Some("section"),
+                    );
+                    for line in logs {
+                        push(line.clone(), None);
+                    }
+                }
+                Some(Err(e)) => push(format!("-- {name}: unavailable ({e})"), Some("section")),
+                None => {}
+            }
+        }
+    }
+
+    (lines, marks)
+}
+
+/// Options shared by the log reads of every container.
+struct ReadOptions {
+    since_time: Option<Timestamp>,
+    tail_lines: Option<i64>,
+    timestamps: bool,
+    use_prefix: bool,
+    is_multi_container: bool,
+    lines: Arc<LineOptions>,
+}
+
+async fn read_instance(
+    target: &ResolvedContainer,
+    previous: bool,
+    opts: &ReadOptions,
+) -> InstanceLogs {
+    let params = LogParams {
+        container: Some(target.container_name.clone()),
+        // The previous instance ended before the window of `since` in most cases,
+        // so it is bounded by a tail instead
+        since_time: if previous { None } else { opts.since_time },
+        tail_lines: opts.tail_lines.or(previous.then_some(PREVIOUS_TAIL_LINES)),
+        timestamps: opts.timestamps,
+        previous,
+        ..LogParams::default()
+    };
+
+    let stream = target
+        .api
+        .log_stream(&target.pod_name, &params)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut lines = stream.lines();
+    let mut out = Vec::new();
+    while let Some(raw) = lines.try_next().await.map_err(|e| e.to_string())? {
+        if let Some(line) = build_log_line(
+            &raw,
+            &target.label,
+            &target.container_name,
+            opts.use_prefix,
+            opts.is_multi_container,
+            &opts.lines,
+        ) {
+            out.push(line.text);
+        }
+    }
+    Ok(out)
+}
+
+async fn crash_context(target: ResolvedContainer, opts: &ReadOptions) -> CrashContext {
+    let status = target.status.as_ref();
+    let termination = status.and_then(Termination::from_status);
+    let previous = match termination {
+        Some(_) => Some(read_instance(&target, true, opts).await),
+        None => None,
+    };
+    let current = read_instance(&target, false, opts).await;
+
+    CrashContext {
+        restart_count: status.map_or(0, |s| s.restart_count),
+        state: status.and_then(|s| s.state.as_ref()).and_then(describe_state),
+        termination,
+        previous,
+        current,
+        pod: target.pod_name,
+        container: target.container_name,
+    }
+}
+
+/// Gather the previous logs, current logs and last termination of each
+/// container into one view, for understanding a crash loop in one step.
+///
+/// Takes the same config as `log_stream_async`; `since` only limits the
+/// current logs, the previous ones get the last 500 lines unless `tail_lines`
+/// is set. Returns `{ containers, lines, marks }`.
+pub async fn crash_logs_async(_lua: mlua::Lua, json: String) -> mlua::Result<String> {
+    let config: LogConfig = serde_json::from_str(&json)
+        .map_err(|e| mlua::Error::external(format!("bad json: {e}")))?;
+
+    let since_time = config
+        .since
+        .as_deref()
+        .and_then(parse_duration)
+        .and_then(|span| Timestamp::now().checked_sub(span).ok());
+
+    with_client(move |client| async move {
+        let lines = Arc::new(LineOptions::new(&config).map_err(mlua::Error::external)?);
+        let pods = match &config.selector {
+            Some(target) => list_selected_pods(&client, target)
+                .await
+                .map_err(mlua::Error::external)?,
+            None => config.pods.clone(),
+        };
+        let targets = resolve_log_targets(
+            &client,
+            &pods,
+            config.container.as_deref(),
+            config.prefix,
+        )
+        .await
+        .map_err(mlua::Error::external)?;
+
+        let opts = ReadOptions {
+            since_time,
+            tail_lines: config.tail_lines,
+            timestamps: config.timestamps.unwrap_or(false),
+            use_prefix: targets.use_prefix,
+            is_multi_container: targets.is_multi_container,
+            lines,
+        };
+        let contexts = futures::future::join_all(
+            targets
+                .containers
+                .into_iter()
+                .map(|target| crash_context(target, &opts)),
+        )
+        .await;
+
+        let (lines, marks) = render(&contexts);
+        serde_json::to_string(&serde_json::json!({
+            "containers": contexts,
+            "lines": lines,
+            "marks": marks,
+        }))
+        .map_err(|e| mlua::Error::external(format!("json encode error: {e}")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{ContainerStateTerminated, ContainerStateWaiting};
+
+    #[test]
+    fn renders_termination_and_both_instances() {
+        let status = ContainerStatus {
+            name: "app".into(),
+            restart_count: 4,
+            last_state: Some(ContainerState {
+                terminated: Some(ContainerStateTerminated {
+                    reason: Some("OOMKilled".into()),
+                    exit_code: 137,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            state: Some(ContainerState {
+                waiting: Some(ContainerStateWaiting {
+                    reason: Some("CrashLoopBackOff".into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let ctx = CrashContext {
+            pod: "web".into(),
+            container: "app".into(),
+            restart_count: status.restart_count,
+            state: status.state.as_ref().and_then(describe_state),
+            termination: Termination::from_status(&status),
+            previous: Some(Ok(vec!["allocating".into()])),
+            current: Err("container is waiting to start".into()),
+        };
+
+        let (lines, marks) = render(&[ctx]);
+        assert_eq!(
+            lines,
+            vec![
+                "== web/app: waiting: CrashLoopBackOff, 4 restarts",
+                "== last exit: OOMKilled, exit code 137",
+                "-- previous: 1 line",
+                "allocating",
+                "-- current: unavailable (container is waiting to start)",
+            ]
+        );
+        let kinds: Vec<_> = marks.iter().map(|m| (m.row, m.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![(0, "container"), (1, "termination"), (2, "section"), (4, "section")]
+        );
+    }
+
+    #[test]
+    fn termination_needs_a_terminated_last_state() {
+        let status = ContainerStatus {
+            name: "app".into(),
+            ..Default::default()
+        };
+        assert_eq!(Termination::from_status(&status), None);
+
+        let waiting = ContainerStatus {
+            last_state: Some(ContainerState {
+                waiting: Some(ContainerStateWaiting::default()),
+                ..Default::default()
+            }),
+            ..status
+        };
+        assert_eq!(Termination::from_status(&waiting), None);
+    }
+}
@@ -0,0 +1,499 @@
+use flate2::{write::GzEncoder, Compression};
+use jiff::Timestamp;
+use k8s_openapi::serde_json;
+use mlua::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::error;
+
+use super::log_session::{
+    find_timestamp, parse_duration, strip_line_timestamp, LogLine, LogSession,
+};
+use crate::structs::{LogConfig, LogExportOptions};
+use crate::{block_on, RUNTIME};
+
+static EXPORT_MAP: OnceLock<Mutex<HashMap<usize, ExportData>>> = OnceLock::new();
+static EXPORT_COUNTER: AtomicUsize = AtomicUsize::new(1);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+const BATCH_SIZE: usize = 1000;
+const DEFAULT_MAX_FILES: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExportFormat {
+    Plain,
+    Jsonl,
+}
+
+impl ExportFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExportFormat::Plain => "plain",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// Counters shared between an export task and `log_export_list`.
+#[derive(Default)]
+struct ExportProgress {
+    lines: AtomicU64,
+    bytes: AtomicU64,
+    rotations: AtomicUsize,
+    done: AtomicBool,
+    error: Mutex<Option<String>>,
+}
+
+struct ExportData {
+    handle: JoinHandle<()>,
+    cancel: CancellationToken,
+    path: PathBuf,
+    format: ExportFormat,
+    gzip: bool,
+    started: Timestamp,
+    progress: Arc<ExportProgress>,
+}
+
+fn export_map() -> &'static Mutex<HashMap<usize, ExportData>> {
+    EXPORT_MAP.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Start writing a log stream to a file in the background.
+///
+/// The export owns its own log session, so it keeps running when the log
+/// buffer is closed. Follows by default; with `follow = false` it ends once
+/// the existing logs are written. Returns an id for `log_export_stop`.
+pub fn log_export_start(
+    _lua: &Lua,
+    (mut config, options): (LogConfig, LogExportOptions),
+) -> LuaResult<usize> {
+    let format = match options.format.as_deref().unwrap_or("plain") {
+        "plain" | "text" => ExportFormat::Plain,
+        "jsonl" | "json" => ExportFormat::Jsonl,
+        other => {
+            return Err(LuaError::RuntimeError(format!(
+                "Invalid export format: {other} (expected plain|jsonl)"
+            )))
+        }
+    };
+    let deadline = match options.duration.as_deref() {
+        Some(d) => {
+            let span = parse_duration(d)
+                .ok_or_else(|| LuaError::RuntimeError(format!("Invalid duration: {d}")))?;
+            let secs = span.total(jiff::Unit::Second).map_err(LuaError::external)?;
+            Some(Instant::now() + Duration::from_secs_f64(secs.max(0.0)))
+        }
+        None => None,
+    };
+
+    let max_files = match options.max_files {
+        Some(0) => {
+            return Err(LuaError::RuntimeError(
+                "max_files must be at least 1".into(),
+            ))
+        }
+        max_files => max_files.unwrap_or(DEFAULT_MAX_FILES),
+    };
+
+    let path = PathBuf::from(&options.path);
+    let gzip = options.gzip.unwrap_or(false);
+    let mut sink = ExportSink::create(
+        path.clone(),
+        format,
+        gzip,
+        options.max_bytes.filter(|b| *b > 0),
+        max_files,
+    )
+    .map_err(|e| LuaError::RuntimeError(format!("open {} failed: {e}", path.display())))?;
+    sink.keep_timestamps = config.timestamps.unwrap_or(false);
+
+    // Timestamps are always requested for the JSONL field; plain output drops
+    // them again unless the caller asked for them.
+    config.timestamps = Some(true);
+    config.follow.get_or_insert(true);
+    let session =
+        crate::with_stream_client(|client| async move { LogSession::new(client, config) })?;
+
+    let rt = RUNTIME
+        .get()
+        .ok_or_else(|| LuaError::runtime("Tokio runtime not initialized"))?;
+    let id = EXPORT_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let cancel = CancellationToken::new();
+    let progress = Arc::new(ExportProgress::default());
+
+    let handle = {
+        let cancel = cancel.clone();
+        let progress = progress.clone();
+        rt.spawn_blocking(move || {
+            let result = run_export(&session, &mut sink, &cancel, deadline, &progress);
+            session.close();
+            let result = result.and_then(|_| sink.finish());
+            if let Err(e) = result {
+                error!("log export #{id}: {e}");
+                if let Ok(mut slot) = progress.error.lock() {
+                    *slot = Some(e.to_string());
+                }
+            }
+            progress.done.store(true, Ordering::Release);
+        })
+    };
+
+    export_map()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("poisoned EXPORT_MAP lock".into()))?
+        .insert(
+            id,
+            ExportData {
+                handle,
+                cancel,
+                path,
+                format,
+                gzip,
+                started: Timestamp::now(),
+                progress,
+            },
+        );
+
+    Ok(id)
+}
+
+pub fn log_export_list(lua: &Lua, _: ()) -> LuaResult<LuaTable> {
+    let table = lua.create_table()?;
+    let map = export_map()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("poisoned EXPORT_MAP lock".into()))?;
+
+    for (id, export) in map.iter() {
+        let progress = &export.progress;
+        let error = progress.error.lock().ok().and_then(|e| e.clone());
+        let status = match (progress.done.load(Ordering::Acquire), &error) {
+            (_, Some(_)) => "failed",
+            (true, None) => "done",
+            (false, None) => "running",
+        };
+
+        let row = lua.create_table()?;
+        row.set("id", *id)?;
+        row.set("path", export.path.display().to_string())?;
+        row.set("format", export.format.as_str())?;
+        row.set("gzip", export.gzip)?;
+        row.set("started", export.started.to_string())?;
+        row.set("status", status)?;
+        row.set("lines", progress.lines.load(Ordering::Relaxed))?;
+        row.set("bytes", progress.bytes.load(Ordering::Relaxed))?;
+        row.set("rotations", progress.rotations.load(Ordering::Relaxed))?;
+        row.set("error", error)?;
+        table.set(*id, row)?;
+    }
+
+    Ok(table)
+}
+
+/// Stop an export, flushing what was received so far, and forget it.
+///
+/// Exports that finished or failed on their own stay listed with their status
+/// and error until they are stopped here.
+pub fn log_export_stop(_lua: &Lua, id: usize) -> LuaResult<()> {
+    let data = export_map()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("poisoned EXPORT_MAP lock".into()))?
+        .remove(&id);
+
+    match data {
+        Some(data) => {
+            data.cancel.cancel();
+            let _ = block_on(data.handle);
+            Ok(())
+        }
+        None => Err(LuaError::RuntimeError(format!(
+            "No log export found for id {id}"
+        ))),
+    }
+}
+
+/// Poll the session and write lines until it ends, is cancelled or the deadline
+/// passes. Lines already received when stopping are still written.
+fn run_export(
+    session: &LogSession,
+    sink: &mut ExportSink,
+    cancel: &CancellationToken,
+    deadline: Option<Instant>,
+    progress: &ExportProgress,
+) -> io::Result<()> {
+    loop {
+        let stop = cancel.is_cancelled() || deadline.is_some_and(|d| Instant::now() >= d);
+        if stop {
+            session.close();
+        }
+
+        let lines = session.take_lines(BATCH_SIZE).map_err(io::Error::other)?;
+        if lines.is_empty() {
+            if stop || !session.is_open() {
+                return Ok(());
+            }
+            std::thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+
+        for line in lines {
+            let written = sink.write_line(line)?;
+            progress.lines.fetch_add(1, Ordering::Relaxed);
+            progress.bytes.fetch_add(written as u64, Ordering::Relaxed);
+        }
+        progress.rotations.store(sink.rotations, Ordering::Relaxed);
+    }
+}
+
+#[derive(Serialize)]
+struct JsonlLine<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pod: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    container: Option<&'a str>,
+    message: &'a str,
+}
+
+enum FileWriter {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl FileWriter {
+    fn create(path: &Path, gzip: bool) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(if gzip {
+            FileWriter::Gzip(GzEncoder::new(file, Compression::default()))
+        } else {
+            FileWriter::Plain(file)
+        })
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            FileWriter::Plain(mut w) => w.flush(),
+            FileWriter::Gzip(w) => w.finish()?.flush(),
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            FileWriter::Plain(w) => w.write_all(buf),
+            FileWriter::Gzip(w) => w.write_all(buf),
+        }
+    }
+}
+
+struct ExportSink {
+    writer: Option<FileWriter>,
+    path: PathBuf,
+    format: ExportFormat,
+    gzip: bool,
+    keep_timestamps: bool,
+    max_bytes: Option<u64>,
+    max_files: usize,
+    /// Uncompressed bytes in the current file
+    written: u64,
+    rotations: usize,
+}
+
+impl ExportSink {
+    fn create(
+        path: PathBuf,
+        format: ExportFormat,
+        gzip: bool,
+        max_bytes: Option<u64>,
+        max_files: usize,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            writer: Some(FileWriter::create(&path, gzip)?),
+            path,
+            format,
+            gzip,
+            keep_timestamps: false,
+            max_bytes,
+            max_files,
+            written: 0,
+            rotations: 0,
+        })
+    }
+
+    /// Encode and write one line, rotating first if it would exceed `max_bytes`.
+    /// Returns the number of uncompressed bytes written.
+    fn write_line(&mut self, mut line: LogLine) -> io::Result<usize> {
+        let timestamp = find_timestamp(&line.text[line.prefix_len..]);
+        if timestamp.is_some() && (self.format == ExportFormat::Jsonl || !self.keep_timestamps) {
+            strip_line_timestamp(&mut line);
+        }
+
+        let mut encoded = match self.format {
+            ExportFormat::Plain => line.text,
+            ExportFormat::Jsonl => {
+                let source = line.source.as_deref();
+                serde_json::to_string(&JsonlLine {
+                    timestamp: timestamp.map(|ts| ts.to_string()),
+                    pod: source.map(|s| s.pod.as_str()),
+                    container: source.map(|s| s.container.as_str()),
+                    message: &line.text[line.prefix_len..],
+                })
+                .map_err(io::Error::other)?
+            }
+        };
+        encoded.push('\n');
+
+        let len = encoded.len() as u64;
+        if self
+            .max_bytes
+            .is_some_and(|max| self.written > 0 && self.written + len > max)
+        {
+            self.rotate()?;
+        }
+
+        self.writer
+            .as_mut()
+            .ok_or_else(|| io::Error::other("export file closed"))?
+            .write_all(encoded.as_bytes())?;
+        self.written += len;
+        Ok(encoded.len())
+    }
+
+    /// Close the current file and shift `path` -> `path.1` -> ... -> `path.N`,
+    /// keeping the `.gz` extension last for gzip exports (`app.log.1.gz`).
+    fn rotate(&mut self) -> io::Result<()> {
+        if let Some(writer) = self.writer.take() {
+            writer.finish()?;
+        }
+        for n in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, n, self.gzip);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, n + 1, self.gzip))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1, self.gzip))?;
+        self.writer = Some(FileWriter::create(&self.path, self.gzip)?);
+        self.written = 0;
+        self.rotations += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.writer.take() {
+            Some(writer) => writer.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+fn rotated_path(path: &Path, n: usize, gzip: bool) -> PathBuf {
+    let name = path.to_string_lossy();
+    let stem = name.strip_suffix(".gz").filter(|_| gzip).unwrap_or(&name);
+    let ext = if gzip { ".gz" } else { "" };
+    PathBuf::from(format!("{stem}.{n}{ext}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::log_session::LogSource;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("kubectl-export-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rotation_shifts_files_and_drops_the_oldest() {
+        let dir = temp_dir("rotate");
+        let path = dir.join("app.log");
+        let mut sink =
+            ExportSink::create(path.clone(), ExportFormat::Plain, false, Some(10), 2).unwrap();
+        for n in 1..=4 {
+            sink.write_line(LogLine::from(format!("line-{n}"))).unwrap();
+        }
+        sink.finish().unwrap();
+
+        assert_eq!(sink.rotations, 3);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "line-4\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("app.log.1")).unwrap(),
+            "line-3\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("app.log.2")).unwrap(),
+            "line-2\n"
+        );
+        assert!(!dir.join("app.log.3").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn jsonl_moves_source_and_timestamp_into_fields() {
+        let dir = temp_dir("jsonl");
+        let path = dir.join("app.jsonl");
+        let mut sink =
+            ExportSink::create(path.clone(), ExportFormat::Jsonl, false, None, 1).unwrap();
+        let prefix = "[web-0/app] ";
+        sink.write_line(LogLine {
+            text: format!("{prefix}2024-01-15T10:30:45Z started"),
+            prefix_len: prefix.len(),
+            source: Some(Arc::new(LogSource {
+                pod: "web-0".into(),
+                container: "app".into(),
+            })),
+            ..LogLine::default()
+        })
+        .unwrap();
+        sink.write_line(LogLine::from("-- status --".to_string()))
+            .unwrap();
+        sink.finish().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            concat!(
+                r#"{"timestamp":"2024-01-15T10:30:45Z","pod":"web-0","container":"app","message":"started"}"#,
+                "\n",
+                r#"{"message":"-- status --"}"#,
+                "\n",
+            )
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn gzip_rotations_keep_the_extension_and_decompress() {
+        let dir = temp_dir("gzip");
+        let path = dir.join("app.log.gz");
+        let mut sink =
+            ExportSink::create(path.clone(), ExportFormat::Plain, true, Some(10), 5).unwrap();
+        sink.write_line(LogLine::from("first".to_string())).unwrap();
+        sink.write_line(LogLine::from("second".to_string()))
+            .unwrap();
+        sink.finish().unwrap();
+
+        let gunzip = |path: PathBuf| {
+            let mut text = String::new();
+            GzDecoder::new(File::open(path).unwrap())
+                .read_to_string(&mut text)
+                .unwrap();
+            text
+        };
+        assert_eq!(gunzip(dir.join("app.log.1.gz")), "first\n");
+        assert_eq!(gunzip(path), "second\n");
+        assert!(!dir.join("app.log.gz.1").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use k8s_openapi::serde_json;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::log_parse::LogRecord;
+
+/// Severity recognized in common log formats, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s.to_ascii_lowercase().as_str() {
+            "trace" | "trc" => LogLevel::Trace,
+            "debug" | "dbg" => LogLevel::Debug,
+            "info" | "inf" | "notice" => LogLevel::Info,
+            "warn" | "warning" | "wrn" => LogLevel::Warn,
+            "error" | "err" | "eror" => LogLevel::Error,
+            "fatal" | "critical" | "crit" | "panic" | "emerg" | "alert" => LogLevel::Fatal,
+            _ => return None,
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+            LogLevel::Fatal => "fatal",
+        }
+    }
+}
+
+/// Detect the level of a log line. Recognizes JSON (`level`, `lvl`, `severity`),
+/// logfmt (`level=warn`), klog (`E0115 10:30:45...`) and a bare level word among
+/// the first few tokens (`[ERROR]`, `WARN:`). A leading RFC 3339 timestamp is skipped.
+pub fn detect_level(line: &str) -> Option<LogLevel> {
+    let line = skip_timestamp(line.trim_start());
+
+    if line.starts_with('{') {
+        if let Ok(serde_json::Value::Object(map)) = serde_json::from_str(line) {
+            return ["level", "lvl", "severity", "log.level"]
+                .iter()
+                .find_map(|k| map.get(*k).and_then(|v| v.as_str()))
+                .and_then(LogLevel::parse);
+        }
+    }
+
+    if let Some(level) = klog_level(line) {
+        return Some(level);
+    }
+
+    for token in line.split_whitespace() {
+        if let Some(value) = token
+            .strip_prefix("level=")
+            .or_else(|| token.strip_prefix("lvl="))
+        {
+            return LogLevel::parse(value.trim_matches('"'));
+        }
+    }
+
+    line.split_whitespace().take(4).find_map(|token| {
+        LogLevel::parse(token.trim_matches(|c: char| !c.is_ascii_alphabetic()))
+    })
+}
+
+/// klog header: severity letter followed by `MMDD`, e.g. `I0115`.
+fn klog_level(line: &str) -> Option<LogLevel> {
+    let bytes = line.as_bytes();
+    if bytes.len() < 6 || !bytes[1..5].iter().all(u8::is_ascii_digit) || bytes[5] != b' ' {
+        return None;
+    }
+    match bytes[0] {
+        b'I' => Some(LogLevel::Info),
+        b'W' => Some(LogLevel::Warn),
+        b'E' => Some(LogLevel::Error),
+        b'F' => Some(LogLevel::Fatal),
+        _ => None,
+    }
+}
+
+/// Drop a leading `2024-01-15T10:30:45.123Z ` as added by `timestamps: true`.
+pub fn skip_timestamp(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((first, rest))
+            if first.len() >= 20
+                && first.as_bytes()[0].is_ascii_digit()
+                && first.as_bytes()[10] == b'T' =>
+        {
+            rest
+        }
+        _ => line,
+    }
+}
+
+/// Include/exclude regexes, a minimum level and per-field regexes, applied to
+/// lines before they are sent to Lua.
+///
+/// Lines without a recognizable level always pass the level check so that
+/// continuation lines such as stack traces are kept. Field filters need a
+/// structured record, so plain-text lines never pass them.
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    include: Option<Regex>,
+    exclude: Option<Regex>,
+    min_level: Option<LogLevel>,
+    fields: Vec<(String, Regex)>,
+}
+
+impl LogFilter {
+    /// Returns `Ok(None)` when no filter is configured.
+    pub fn new(
+        include: Option<&str>,
+        exclude: Option<&str>,
+        level: Option<&str>,
+        fields: Option<&HashMap<String, String>>,
+    ) -> Result<Option<Self>, String> {
+        let compile = |pattern: Option<&str>, what: &str| {
+            pattern
+                .filter(|p| !p.is_empty())
+                .map(|p| Regex::new(p).map_err(|e| format!("invalid {what} pattern: {e}")))
+                .transpose()
+        };
+
+        let include = compile(include, "include")?;
+        let exclude = compile(exclude, "exclude")?;
+        let min_level = match level.filter(|l| !l.is_empty()) {
+            Some(l) => Some(LogLevel::parse(l).ok_or_else(|| format!("unknown log level: {l}"))?),
+            None => None,
+        };
+
+        let mut fields = fields
+            .into_iter()
+            .flatten()
+            .map(|(key, pattern)| {
+                Regex::new(pattern)
+                    .map(|re| (key.clone(), re))
+                    .map_err(|e| format!("invalid pattern for field {key}: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if include.is_none() && exclude.is_none() && min_level.is_none() && fields.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            include,
+            exclude,
+            min_level,
+            fields,
+        }))
+    }
+
+    /// Whether a record is needed to evaluate this filter.
+    pub fn has_field_filters(&self) -> bool {
+        !self.fields.is_empty()
+    }
+
+    /// `record` is the parsed form of `line`, if it is structured.
+    pub fn matches(&self, line: &str, record: Option<&LogRecord>) -> bool {
+        if self.include.as_ref().is_some_and(|re| !re.is_match(line)) {
+            return false;
+        }
+        if self.exclude.as_ref().is_some_and(|re| re.is_match(line)) {
+            return false;
+        }
+        if let Some(min) = self.min_level {
+            let level = record.and_then(|r| r.level).or_else(|| detect_level(line));
+            if level.is_some_and(|level| level < min) {
+                return false;
+            }
+        }
+        self.fields.iter().all(|(key, re)| {
+            record
+                .and_then(|r| r.field(key))
+                .is_some_and(|value| re.is_match(value))
+        })
+    }
+
+    /// Byte ranges `[start, end)` of include matches, for highlighting.
+    pub fn spans(&self, line: &str) -> Vec<(usize, usize)> {
+        self.include
+            .as_ref()
+            .map(|re| re.find_iter(line).map(|m| (m.start(), m.end())).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::log_parse::parse_record;
+
+    #[test]
+    fn detects_common_formats() {
+        assert_eq!(
+            detect_level(r#"{"level":"warn","msg":"slow"}"#),
+            Some(LogLevel::Warn)
+        );
+        assert_eq!(
+            detect_level("ts=2024 level=error msg=boom"),
+            Some(LogLevel::Error)
+        );
+        assert_eq!(
+            detect_level("E0115 10:30:45.123456 1 controller.go:12] failed"),
+            Some(LogLevel::Error)
+        );
+        assert_eq!(
+            detect_level("2024-01-15T10:30:45.123Z [INFO] started"),
+            Some(LogLevel::Info)
+        );
+        assert_eq!(detect_level("GET /healthz 200"), None);
+    }
+
+    #[test]
+    fn filter_combines_include_exclude_and_level() {
+        let filter = LogFilter::new(Some("api"), Some("healthz"), Some("warn"), None)
+            .unwrap()
+            .unwrap();
+        assert!(filter.matches("ERROR api call failed", None));
+        assert!(!filter.matches("INFO api call ok", None));
+        assert!(!filter.matches("ERROR api healthz failed", None));
+        assert!(!filter.matches("ERROR db down", None));
+        // No level: kept
+        assert!(filter.matches("    at api.handler(api.go:10)", None));
+    }
+
+    #[test]
+    fn field_filters_need_a_record() {
+        let fields = HashMap::from([("user".to_owned(), "^bob$".to_owned())]);
+        let filter = LogFilter::new(None, None, None, Some(&fields)).unwrap().unwrap();
+        let line = "level=info msg=login user=bob";
+        let record = parse_record(line, &[]);
+        assert!(filter.matches(line, record.as_ref()));
+        assert!(!filter.matches("login by bob", None));
+    }
+
+    #[test]
+    fn spans_and_empty_config() {
+        let filter = LogFilter::new(Some("o+"), None, None, None).unwrap().unwrap();
+        assert_eq!(filter.spans("foo boo"), vec![(1, 3), (5, 7)]);
+        assert!(LogFilter::new(None, Some(""), None, None).unwrap().is_none());
+        assert!(LogFilter::new(Some("("), None, None, None).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use k8s_openapi::serde_json;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+static BUFFER_MAP: OnceLock<Mutex<HashMap<usize, Arc<Mutex<LogBuffer>>>>> = OnceLock::new();
+static BUFFER_COUNTER: AtomicUsize = AtomicUsize::new(1);
+
+/// Lines kept per session when `buffer_lines` is not set.
+pub const DEFAULT_BUFFER_LINES: usize = 250_000;
+
+/// Matching lines returned by a search when no limit is given.
+const DEFAULT_SEARCH_LIMIT: usize = 1000;
+
+fn buffer_map() -> &'static Mutex<HashMap<usize, Arc<Mutex<LogBuffer>>>> {
+    BUFFER_MAP.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The last `capacity` lines a session delivered, numbered from 1 in delivery order.
+///
+/// Line numbers stay stable when old lines are evicted, so they can be mapped to
+/// buffer rows by adding the row the session started at.
+#[derive(Debug)]
+pub struct LogBuffer {
+    lines: VecDeque<Arc<str>>,
+    /// Number of the oldest retained line
+    first: usize,
+    capacity: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SearchMatch {
+    pub line: usize,
+    /// Matches on this line
+    pub count: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SearchResult {
+    /// Retained line range that was searched
+    pub first: usize,
+    pub last: usize,
+    /// Matches over all searched lines
+    pub total: usize,
+    /// Lines with at least one match
+    pub lines: usize,
+    /// The first `limit` matching lines
+    pub matches: Vec<SearchMatch>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ContextLine {
+    pub line: usize,
+    pub text: String,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            first: 1,
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, line: &str) {
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+            self.first += 1;
+        }
+        self.lines.push_back(Arc::from(line));
+    }
+
+    /// Cheap copy of the retained lines, so a scan does not hold the lock.
+    fn snapshot(&self) -> (usize, Vec<Arc<str>>) {
+        (self.first, self.lines.iter().cloned().collect())
+    }
+
+    /// Lines `line - n ..= line + n` that are still retained.
+    pub fn context(&self, line: usize, n: usize) -> Vec<ContextLine> {
+        let start = line.saturating_sub(n).max(self.first);
+        let end = line.saturating_add(n).min(self.first + self.lines.len());
+        (start..=end)
+            .filter_map(|number| {
+                let text = self.lines.get(number.checked_sub(self.first)?)?;
+                Some(ContextLine {
+                    line: number,
+                    text: text.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn scan(first: usize, lines: &[Arc<str>], re: &Regex, limit: usize) -> SearchResult {
+    let mut result = SearchResult {
+        first,
+        last: (first + lines.len()).saturating_sub(1),
+        total: 0,
+        lines: 0,
+        matches: Vec::new(),
+    };
+    for (i, text) in lines.iter().enumerate() {
+        let count = re.find_iter(text).count();
+        if count == 0 {
+            continue;
+        }
+        result.total += count;
+        result.lines += 1;
+        if result.matches.len() < limit {
+            result.matches.push(SearchMatch {
+                line: first + i,
+                count,
+            });
+        }
+    }
+    result
+}
+
+/// Register a buffer so it can be searched from a worker thread by id.
+pub fn register(buffer: Arc<Mutex<LogBuffer>>) -> usize {
+    let id = BUFFER_COUNTER.fetch_add(1, Ordering::SeqCst);
+    if let Ok(mut map) = buffer_map().lock() {
+        map.insert(id, buffer);
+    }
+    id
+}
+
+pub fn unregister(id: usize) {
+    if let Ok(mut map) = buffer_map().lock() {
+        map.remove(&id);
+    }
+}
+
+/// Search a buffer for a regex. Only the snapshot is taken under the lock.
+pub fn search(
+    buffer: &Mutex<LogBuffer>,
+    pattern: &str,
+    limit: Option<usize>,
+) -> Result<SearchResult, String> {
+    let re = Regex::new(pattern).map_err(|e| format!("invalid search pattern: {e}"))?;
+    let (first, lines) = buffer
+        .lock()
+        .map_err(|_| "poisoned log buffer lock".to_string())?
+        .snapshot();
+    Ok(scan(first, &lines, &re, limit.unwrap_or(DEFAULT_SEARCH_LIMIT)))
+}
+
+#[derive(Deserialize)]
+struct SearchArgs {
+    session: usize,
+    pattern: String,
+    limit: Option<usize>,
+}
+
+/// Search a session's buffer off the main thread, via `commands.run_async`.
+/// Takes `{ session, pattern, limit? }` where `session` is the session's `id()`.
+pub async fn log_search_async(_lua: mlua::Lua, json: String) -> mlua::Result<String> {
+    let args: SearchArgs = serde_json::from_str(&json)
+        .map_err(|e| mlua::Error::external(format!("bad json: {e}")))?;
+
+    let buffer = buffer_map()
+        .lock()
+        .map_err(|_| mlua::Error::external("poisoned BUFFER_MAP lock"))?
+        .get(&args.session)
+        .cloned()
+        .ok_or_else(|| {
+            mlua::Error::external(format!("No log session found for id {}", args.session))
+        })?;
+
+    let result = search(&buffer, &args.pattern, args.limit).map_err(mlua::Error::external)?;
+    serde_json::to_string(&result)
+        .map_err(|e| mlua::Error::external(format!("json encode error: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_keeps_line_numbers_after_eviction() {
+        let buffer = Mutex::new(LogBuffer::new(3));
+        for line in ["error a", "ok", "error b error c", "ok", "error d"] {
+            buffer.lock().unwrap().push(line);
+        }
+
+        let result = search(&buffer, "error", None).unwrap();
+        assert_eq!((result.first, result.last), (3, 5));
+        assert_eq!((result.total, result.lines), (3, 2));
+        assert_eq!(
+            result.matches,
+            vec![
+                SearchMatch { line: 3, count: 2 },
+                SearchMatch { line: 5, count: 1 },
+            ]
+        );
+
+        let context = buffer.lock().unwrap().context(4, 5);
+        let numbers: Vec<_> = context.iter().map(|c| c.line).collect();
+        assert_eq!(numbers, vec![3, 4, 5]);
+        assert!(buffer.lock().unwrap().context(1, 0).is_empty());
+        assert!(search(&buffer, "(", None).is_err());
+    }
+}
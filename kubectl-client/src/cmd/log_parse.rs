@@ -0,0 +1,195 @@
+use k8s_openapi::serde_json::{self, Value};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::log_filter::{skip_timestamp, LogLevel};
+
+const TIMESTAMP_KEYS: &[&str] = &["ts", "time", "timestamp", "@timestamp", "t"];
+const LEVEL_KEYS: &[&str] = &["level", "lvl", "severity", "log.level"];
+const MESSAGE_KEYS: &[&str] = &["msg", "message", "log", "event"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Json,
+    Logfmt,
+}
+
+/// A JSON or logfmt line split into its common fields.
+///
+/// `fields` only holds the keys requested by the session; every key is still
+/// available to field filters through [`LogRecord::field`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogRecord {
+    pub format: LogFormat,
+    pub timestamp: Option<String>,
+    pub level: Option<LogLevel>,
+    pub msg: Option<String>,
+    pub fields: BTreeMap<String, String>,
+    /// All keys, nested JSON objects flattened to `a.b`
+    #[serde(skip)]
+    values: BTreeMap<String, String>,
+}
+
+impl LogRecord {
+    /// Value of `key`, where `timestamp`, `level` and `msg` also resolve their aliases.
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str).or(match key {
+            "timestamp" => self.timestamp.as_deref(),
+            "level" => self.level.map(LogLevel::as_str),
+            "msg" => self.msg.as_deref(),
+            _ => None,
+        })
+    }
+}
+
+/// Parse a JSON object or logfmt line. A leading `timestamps: true` timestamp is
+/// skipped and used when the payload has none of its own. Plain text returns `None`.
+pub fn parse_record(line: &str, keys: &[String]) -> Option<LogRecord> {
+    let line = line.trim();
+    let body = skip_timestamp(line);
+    let line_timestamp = line.strip_suffix(body).map(str::trim).filter(|t| !t.is_empty());
+
+    let (format, values) = if body.starts_with('{') {
+        (LogFormat::Json, parse_json(body)?)
+    } else {
+        (LogFormat::Logfmt, parse_logfmt(body)?)
+    };
+
+    let lookup = |aliases: &[&str]| aliases.iter().find_map(|k| values.get(*k).cloned());
+
+    Some(LogRecord {
+        format,
+        timestamp: lookup(TIMESTAMP_KEYS).or_else(|| line_timestamp.map(str::to_owned)),
+        level: lookup(LEVEL_KEYS).as_deref().and_then(LogLevel::parse),
+        msg: lookup(MESSAGE_KEYS),
+        fields: keys
+            .iter()
+            .filter_map(|k| values.get(k).map(|v| (k.clone(), v.clone())))
+            .collect(),
+        values,
+    })
+}
+
+fn parse_json(body: &str) -> Option<BTreeMap<String, String>> {
+    let value: Value = serde_json::from_str(body).ok()?;
+    if !value.is_object() {
+        return None;
+    }
+    let mut out = BTreeMap::new();
+    flatten("", &value, &mut out);
+    Some(out)
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{prefix}.{k}")
+                };
+                flatten(&key, v, out);
+            }
+        }
+        Value::Null => {}
+        Value::String(s) => {
+            out.insert(prefix.to_owned(), s.clone());
+        }
+        other => {
+            out.insert(prefix.to_owned(), other.to_string());
+        }
+    }
+}
+
+/// Parse `key=value key2="quoted value"` pairs. To keep plain text out, every
+/// token must be a pair, there must be at least two, and one of them must be a
+/// level or message key.
+fn parse_logfmt(body: &str) -> Option<BTreeMap<String, String>> {
+    let mut out = BTreeMap::new();
+    let mut rest = body.trim_start();
+
+    while !rest.is_empty() {
+        let (key, after) = rest.split_once('=')?;
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return None;
+        }
+        let value = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let (value, remaining) = read_quoted(quoted)?;
+                rest = remaining;
+                value
+            }
+            None => {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                rest = &after[end..];
+                after[..end].to_owned()
+            }
+        };
+        out.insert(key.to_owned(), value);
+        rest = rest.trim_start();
+    }
+
+    let has_known_key = LEVEL_KEYS.iter().chain(MESSAGE_KEYS).any(|k| out.contains_key(*k));
+    (out.len() >= 2 && has_known_key).then_some(out)
+}
+
+/// Read up to the closing quote, unescaping `\"`, `\\`, `\n` and `\t`.
+fn read_quoted(s: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &s[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                other => value.push(other),
+            },
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_json() {
+        let keys = vec!["http.status".to_owned()];
+        let record = parse_record(
+            r#"{"time":"2024-01-15T10:30:45Z","level":"WARN","msg":"slow","http":{"status":503}}"#,
+            &keys,
+        )
+        .unwrap();
+        assert_eq!(record.format, LogFormat::Json);
+        assert_eq!(record.level, Some(LogLevel::Warn));
+        assert_eq!(record.msg.as_deref(), Some("slow"));
+        assert_eq!(record.timestamp.as_deref(), Some("2024-01-15T10:30:45Z"));
+        assert_eq!(record.fields.get("http.status").map(String::as_str), Some("503"));
+    }
+
+    #[test]
+    fn parses_logfmt_with_kube_timestamp() {
+        let record = parse_record(
+            r#"2024-01-15T10:30:45.123456789Z level=info msg="user \"bob\" logged in" user=bob"#,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(record.format, LogFormat::Logfmt);
+        assert_eq!(record.msg.as_deref(), Some(r#"user "bob" logged in"#));
+        assert_eq!(record.timestamp.as_deref(), Some("2024-01-15T10:30:45.123456789Z"));
+        assert_eq!(record.field("user"), Some("bob"));
+        assert_eq!(record.field("level"), Some("info"));
+    }
+
+    #[test]
+    fn plain_text_is_not_structured() {
+        assert!(parse_record("GET /healthz 200", &[]).is_none());
+        assert!(parse_record("retrying with timeout=5s", &[]).is_none());
+        assert!(parse_record("[1, 2, 3]", &[]).is_none());
+    }
+}
@@ -1,14 +1,26 @@
 use futures::{AsyncBufReadExt, TryStreamExt};
 use jiff::{Span, Timestamp};
-use k8s_openapi::api::core::v1::{Pod, PodSpec};
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{ContainerStatus, Pod, PodSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, OwnerReference};
 use k8s_openapi::serde_json;
-use kube::api::LogParams;
-use kube::{Api, Client};
+use kube::api::{GroupVersionKind, ListParams, LogParams};
+use kube::{Api, Client, ResourceExt};
 use mlua::{prelude::*, UserData, UserDataMethods};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
+use super::log_filter::{detect_level, skip_timestamp, LogFilter, LogLevel};
+use super::log_index::{self, LogBuffer, DEFAULT_BUFFER_LINES};
+use super::log_parse::{parse_record, LogRecord};
 use crate::streaming::{StreamingSession, TaskHandle};
-use crate::structs::{LogConfig, PodRef};
+use crate::store::{self, Subscription, SubscriptionEvent, WatchSelector};
+use crate::structs::{LogConfig, LogSelector, PodRef};
 use crate::{block_on, with_client, RUNTIME};
 
 // ============================================================================
@@ -16,22 +28,26 @@ use crate::{block_on, with_client, RUNTIME};
 // ============================================================================
 
 /// A resolved container target ready for log streaming.
-struct ResolvedContainer {
-    api: Api<Pod>,
-    pod_name: String,
-    container_name: String,
+pub(crate) struct ResolvedContainer {
+    pub(crate) api: Api<Pod>,
+    pub(crate) pod_name: String,
+    pub(crate) container_name: String,
+    /// Name shown in line prefixes: the pod, or the job run it belongs to
+    pub(crate) label: String,
+    /// Status when the target was resolved; restart count and last termination
+    pub(crate) status: Option<ContainerStatus>,
 }
 
 /// Result of resolving log targets from pods.
-struct ResolvedTargets {
-    containers: Vec<ResolvedContainer>,
-    is_multi_container: bool,
-    use_prefix: bool,
+pub(crate) struct ResolvedTargets {
+    pub(crate) containers: Vec<ResolvedContainer>,
+    pub(crate) is_multi_container: bool,
+    pub(crate) use_prefix: bool,
 }
 
 /// Resolve pods and containers into concrete log targets.
 /// Handles pod fetching, container discovery, and prefix logic.
-async fn resolve_log_targets(
+pub(crate) async fn resolve_log_targets(
     client: &Client,
     pods: &[PodRef],
     target_container: Option<&str>,
@@ -65,18 +81,20 @@ async fn resolve_log_targets(
             }
         };
 
-        let spec = match pod.spec {
+        let spec = match &pod.spec {
             Some(s) => s,
             None => continue,
         };
 
-        let container_names = get_container_names(&spec, target_container);
+        let container_names = get_container_names(spec, target_container);
         total_containers += container_names.len();
 
         for container_name in container_names {
             containers.push(ResolvedContainer {
                 api: api.clone(),
                 pod_name: pod_ref.name.clone(),
+                label: pod_ref.run.clone().unwrap_or_else(|| pod_ref.name.clone()),
+                status: container_status(&pod, &container_name),
                 container_name,
             });
         }
@@ -104,28 +122,142 @@ fn get_container_names(spec: &PodSpec, target: Option<&str>) -> Vec<String> {
     names
 }
 
-/// Format a log line with optional pod/container prefix.
-/// Strips ANSI escape codes from the input line.
-fn format_log_line(
-    line: &str,
+/// Status of a regular or init container by name.
+fn container_status(pod: &Pod, name: &str) -> Option<ContainerStatus> {
+    let status = pod.status.as_ref()?;
+    status
+        .container_statuses
+        .iter()
+        .flatten()
+        .chain(status.init_container_statuses.iter().flatten())
+        .find(|cs| cs.name == name)
+        .cloned()
+}
+
+/// The `[pod] ` or `[pod/container] ` prefix of a formatted line, empty without prefix.
+fn log_prefix(
     pod_name: &str,
     container_name: &str,
     use_prefix: bool,
     is_multi_container: bool,
 ) -> String {
-    let clean_line = String::from_utf8_lossy(&strip_ansi_escapes::strip(line)).into_owned();
     if !use_prefix {
-        return clean_line;
-    }
-    if is_multi_container {
-        format!("[{}/{}] {}", pod_name, container_name, clean_line)
+        String::new()
+    } else if is_multi_container {
+        format!("[{}/{}] ", pod_name, container_name)
     } else {
-        format!("[{}] {}", pod_name, clean_line)
+        format!("[{}] ", pod_name)
+    }
+}
+
+/// A formatted line plus the byte ranges the include filter matched and, for
+/// structured sessions, the parsed record.
+#[derive(Debug, Clone, Default)]
+pub struct LogLine {
+    pub text: String,
+    pub spans: Vec<(usize, usize)>,
+    pub record: Option<LogRecord>,
+    /// Length of the `[pod/container] ` prefix in `text`
+    pub prefix_len: usize,
+    /// Container the line came from; `None` for status lines
+    pub source: Option<Arc<LogSource>>,
+}
+
+#[derive(Debug)]
+pub struct LogSource {
+    pub pod: String,
+    pub container: String,
+}
+
+impl From<String> for LogLine {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct LineRecord<'a> {
+    text: &'a str,
+    prefix: &'a str,
+    spans: &'a [(usize, usize)],
+    #[serde(flatten)]
+    record: Option<&'a LogRecord>,
+}
+
+/// How raw lines are filtered and parsed before they are sent to Lua.
+#[derive(Debug, Default)]
+pub(crate) struct LineOptions {
+    filter: Option<LogFilter>,
+    /// Keys to extract into record fields; `None` when structured parsing is off
+    structured: Option<Vec<String>>,
+}
+
+impl LineOptions {
+    pub(crate) fn new(config: &LogConfig) -> Result<Self, String> {
+        let filter = LogFilter::new(
+            config.include.as_deref(),
+            config.exclude.as_deref(),
+            config.level.as_deref(),
+            config.field_filters.as_ref(),
+        )?;
+        let structured = config
+            .structured
+            .unwrap_or(false)
+            .then(|| config.fields.clone().unwrap_or_default());
+        Ok(Self { filter, structured })
+    }
+
+    /// Parse `line` when records are returned or field filters need one.
+    fn record(&self, line: &str) -> Option<LogRecord> {
+        let needs_record = self.structured.is_some()
+            || self.filter.as_ref().is_some_and(LogFilter::has_field_filters);
+        if !needs_record {
+            return None;
+        }
+        parse_record(line, self.structured.as_deref().unwrap_or_default())
+    }
+}
+
+/// Format a log line with optional pod/container prefix, stripping ANSI escape codes.
+/// Returns `None` when the filter drops it. Filters see the line without prefix;
+/// spans are shifted to the formatted text.
+pub(crate) fn build_log_line(
+    line: &str,
+    pod_name: &str,
+    container_name: &str,
+    use_prefix: bool,
+    is_multi_container: bool,
+    options: &LineOptions,
+) -> Option<LogLine> {
+    let clean_line = String::from_utf8_lossy(&strip_ansi_escapes::strip(line)).into_owned();
+    let record = options.record(&clean_line);
+    let filter = options.filter.as_ref();
+    if filter.is_some_and(|f| !f.matches(&clean_line, record.as_ref())) {
+        return None;
     }
+
+    let prefix = log_prefix(pod_name, container_name, use_prefix, is_multi_container);
+    let spans = filter
+        .map(|f| f.spans(&clean_line))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(start, end)| (start + prefix.len(), end + prefix.len()))
+        .collect();
+
+    Some(LogLine {
+        prefix_len: prefix.len(),
+        text: prefix + &clean_line,
+        spans,
+        record: record.filter(|_| options.structured.is_some()),
+        source: None,
+    })
 }
 
 /// Parse a duration string like "5m", "1h", "30s".
-fn parse_duration(input: &str) -> Option<Span> {
+pub(crate) fn parse_duration(input: &str) -> Option<Span> {
     if input.is_empty() || input == "0" || input.len() < 2 {
         return None;
     }
@@ -139,6 +271,394 @@ fn parse_duration(input: &str) -> Option<Span> {
     }
 }
 
+// ============================================================================
+// Selector Targets
+// ============================================================================
+
+/// Render a `LabelSelector` in the string form list/watch calls accept.
+fn label_selector_string(sel: &LabelSelector) -> String {
+    let mut parts: Vec<String> = sel
+        .match_labels
+        .iter()
+        .flatten()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect();
+
+    for expr in sel.match_expressions.iter().flatten() {
+        let values = expr.values.as_deref().unwrap_or_default().join(",");
+        parts.push(match expr.operator.as_str() {
+            "In" => format!("{} in ({})", expr.key, values),
+            "NotIn" => format!("{} notin ({})", expr.key, values),
+            "Exists" => expr.key.clone(),
+            "DoesNotExist" => format!("!{}", expr.key),
+            _ => continue,
+        });
+    }
+    parts.join(",")
+}
+
+/// Turn a `LogSelector` into a pod label selector, looking up the workload if one is named.
+pub(crate) async fn resolve_pod_selector(
+    client: &Client,
+    target: &LogSelector,
+) -> Result<String, String> {
+    let ns = target.namespace.as_str();
+    let selector = match (target.kind.as_deref(), target.name.as_deref()) {
+        (Some(kind), Some(name)) => {
+            let err = |e: kube::Error| format!("Failed to get {kind} {name} in {ns}: {e}");
+            let sel = match kind.to_lowercase().as_str() {
+                "deployment" => {
+                    let obj = Api::<Deployment>::namespaced(client.clone(), ns)
+                        .get(name)
+                        .await
+                        .map_err(err)?;
+                    obj.spec.map(|s| s.selector)
+                }
+                "statefulset" => {
+                    let obj = Api::<StatefulSet>::namespaced(client.clone(), ns)
+                        .get(name)
+                        .await
+                        .map_err(err)?;
+                    obj.spec.map(|s| s.selector)
+                }
+                "daemonset" => {
+                    let obj = Api::<DaemonSet>::namespaced(client.clone(), ns)
+                        .get(name)
+                        .await
+                        .map_err(err)?;
+                    obj.spec.map(|s| s.selector)
+                }
+                "replicaset" => {
+                    let obj = Api::<ReplicaSet>::namespaced(client.clone(), ns)
+                        .get(name)
+                        .await
+                        .map_err(err)?;
+                    obj.spec.map(|s| s.selector)
+                }
+                "job" => {
+                    let obj = Api::<Job>::namespaced(client.clone(), ns)
+                        .get(name)
+                        .await
+                        .map_err(err)?;
+                    obj.spec.and_then(|s| s.selector)
+                }
+                _ => return Err(format!("Cannot follow logs for kind {kind}")),
+            };
+            sel.as_ref().map(label_selector_string).unwrap_or_default()
+        }
+        _ => target.labels.clone().unwrap_or_default(),
+    };
+
+    if selector.trim().is_empty() {
+        return Err("Log selector matches no labels".to_string());
+    }
+    Ok(selector)
+}
+
+/// CronJob runs logged when the selector does not set `runs`.
+const DEFAULT_CRONJOB_RUNS: usize = 3;
+
+/// Whether a Job or CronJob selector names its target, so pods are resolved per run.
+fn is_job_target(target: &LogSelector) -> bool {
+    target.name.is_some()
+        && target
+            .kind
+            .as_deref()
+            .is_some_and(|k| k.eq_ignore_ascii_case("job") || k.eq_ignore_ascii_case("cronjob"))
+}
+
+fn owned_by(owners: Option<&Vec<OwnerReference>>, uid: Option<&str>) -> bool {
+    uid.is_some_and(|uid| owners.into_iter().flatten().any(|o| o.uid == uid))
+}
+
+/// The Job, or the last `runs` Jobs a CronJob created, oldest first.
+async fn selected_jobs(client: &Client, target: &LogSelector) -> Result<Vec<Job>, String> {
+    let ns = target.namespace.as_str();
+    let name = target.name.as_deref().unwrap_or_default();
+    let jobs: Api<Job> = Api::namespaced(client.clone(), ns);
+
+    if target.kind.as_deref().is_some_and(|k| k.eq_ignore_ascii_case("job")) {
+        let job = jobs
+            .get(name)
+            .await
+            .map_err(|e| format!("Failed to get Job {name} in {ns}: {e}"))?;
+        return Ok(vec![job]);
+    }
+
+    let cronjob = Api::<CronJob>::namespaced(client.clone(), ns)
+        .get(name)
+        .await
+        .map_err(|e| format!("Failed to get CronJob {name} in {ns}: {e}"))?;
+    let mut runs: Vec<Job> = jobs
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to list Jobs in {ns}: {e}"))?
+        .items
+        .into_iter()
+        .filter(|job| owned_by(job.metadata.owner_references.as_ref(), cronjob.uid().as_deref()))
+        .collect();
+    if runs.is_empty() {
+        return Err(format!("CronJob {name} has no runs"));
+    }
+
+    runs.sort_by_key(|job| Reverse(job.creation_timestamp()));
+    runs.truncate(target.runs.unwrap_or(DEFAULT_CRONJOB_RUNS).max(1));
+    runs.reverse();
+    Ok(runs)
+}
+
+/// Pods of each job run, including completed and failed ones, matched by owner
+/// reference. Runs with several pods (retries, parallelism) number them `run#n`.
+async fn list_job_pods(client: &Client, target: &LogSelector) -> Result<Vec<PodRef>, String> {
+    let ns = target.namespace.as_str();
+    let api: Api<Pod> = Api::namespaced(client.clone(), ns);
+    let mut refs = Vec::new();
+
+    for job in selected_jobs(client, target).await? {
+        let selector = job
+            .spec
+            .as_ref()
+            .and_then(|s| s.selector.as_ref())
+            .map(label_selector_string)
+            .unwrap_or_default();
+        let mut pods: Vec<Pod> = api
+            .list(&ListParams::default().labels(&selector))
+            .await
+            .map_err(|e| format!("Failed to list pods of Job {}: {e}", job.name_any()))?
+            .items
+            .into_iter()
+            .filter(|pod| owned_by(pod.metadata.owner_references.as_ref(), job.uid().as_deref()))
+            .collect();
+        pods.sort_by_key(|pod| pod.creation_timestamp());
+
+        let run = job.name_any();
+        let attempts = pods.len();
+        refs.extend(pods.iter().enumerate().map(|(i, pod)| PodRef {
+            name: pod.name_any(),
+            namespace: ns.to_string(),
+            run: Some(if attempts > 1 {
+                format!("{run}#{}", i + 1)
+            } else {
+                run.clone()
+            }),
+        }));
+    }
+
+    Ok(refs)
+}
+
+/// List the pods a selector currently matches.
+pub(crate) async fn list_selected_pods(
+    client: &Client,
+    target: &LogSelector,
+) -> Result<Vec<PodRef>, String> {
+    if is_job_target(target) {
+        return list_job_pods(client, target).await;
+    }
+    let selector = resolve_pod_selector(client, target).await?;
+    let api: Api<Pod> = Api::namespaced(client.clone(), &target.namespace);
+    let pods = api
+        .list(&ListParams::default().labels(&selector))
+        .await
+        .map_err(|e| format!("Failed to list pods for {selector}: {e}"))?;
+
+    Ok(pods
+        .items
+        .iter()
+        .map(|pod| PodRef {
+            name: pod.name_any(),
+            namespace: target.namespace.clone(),
+            run: None,
+        })
+        .collect())
+}
+
+/// Names of containers that are running and therefore have a log stream to attach to.
+fn running_containers(pod: &Pod, target: Option<&str>) -> Vec<String> {
+    let Some(status) = &pod.status else {
+        return Vec::new();
+    };
+    status
+        .container_statuses
+        .iter()
+        .flatten()
+        .chain(status.init_container_statuses.iter().flatten())
+        .filter(|cs| cs.state.as_ref().is_some_and(|s| s.running.is_some()))
+        .map(|cs| cs.name.clone())
+        .filter(|name| target.is_none_or(|t| t == name))
+        .collect()
+}
+
+/// Pods followed by a selector-based log session.
+struct FollowTarget {
+    context: String,
+    namespace: String,
+    /// Resolved pod label selector
+    selector: String,
+    container: Option<String>,
+    options: Arc<LineOptions>,
+}
+
+impl FollowTarget {
+    /// Subscribe to the store's Pod reflector for this namespace and selector.
+    async fn subscribe(&self) -> Result<Subscription, String> {
+        store::subscribe(
+            self.context.clone(),
+            GroupVersionKind::gvk("", "v1", "Pod"),
+            Some(self.namespace.clone()),
+            WatchSelector::new(Some(self.selector.clone()), None),
+        )
+        .await
+    }
+}
+
+/// Follow pods in the store's reflector for the target's selector and keep one log
+/// stream per running container, attaching as pods start and detaching when they go away.
+fn spawn_pod_follower(
+    runtime: &tokio::runtime::Runtime,
+    client: Client,
+    follow: FollowTarget,
+    log_sender: mpsc::UnboundedSender<LogLine>,
+    task_handle: TaskHandle,
+    cancel: CancellationToken,
+    params: ContainerLogParams,
+) {
+    let _guard = task_handle.guard();
+    let handle = runtime.handle().clone();
+
+    runtime.spawn(async move {
+        let _guard = _guard;
+        let api: Api<Pod> = Api::namespaced(client, &follow.namespace);
+        let FollowTarget {
+            selector,
+            container,
+            options,
+            ..
+        } = &follow;
+
+        let mut pods = tokio::select! {
+            _ = cancel.cancelled() => return,
+            pods = follow.subscribe() => match pods {
+                Ok(pods) => pods,
+                Err(e) => {
+                    let _ = log_sender.send(format!("[{selector}] watch error: {e}").into());
+                    return;
+                }
+            },
+        };
+
+        // "pod/container" -> token that stops that container's stream
+        let attached: Arc<Mutex<HashMap<String, CancellationToken>>> = Default::default();
+        // Pods in the store when the session starts honour since/tail, later ones are new
+        let mut synced = false;
+        // Repeats of the same error while the watcher backs off are shown once
+        let mut last_error: Option<String> = None;
+
+        loop {
+            let current: Vec<Pod> = pods
+                .objects()
+                .iter()
+                .filter_map(|obj| serde_json::to_value(obj.as_ref()).ok())
+                .filter_map(|value| serde_json::from_value(value).ok())
+                .collect();
+
+            if let Ok(map) = attached.lock() {
+                map.iter()
+                    .filter(|(key, _)| {
+                        let pod_name = key.split_once('/').map_or(key.as_str(), |(pod, _)| pod);
+                        !current.iter().any(|pod| pod.name_any() == pod_name)
+                    })
+                    .for_each(|(_, token)| token.cancel());
+            }
+
+            for pod in &current {
+                let pod_name = pod.name_any();
+                let total = pod.spec.as_ref().map_or(0, |s| s.containers.len());
+                for container_name in running_containers(pod, container.as_deref()) {
+                    let key = format!("{pod_name}/{container_name}");
+                    let token = cancel.child_token();
+                    match attached.lock() {
+                        Ok(mut map) if !map.contains_key(&key) => {
+                            map.insert(key.clone(), token.clone());
+                        }
+                        _ => continue,
+                    }
+
+                    let _ = log_sender.send(format!("+ {key}").into());
+
+                    let params = ContainerLogParams {
+                        since_time: if synced { None } else { params.since_time },
+                        since_seconds: if synced { None } else { params.since_seconds },
+                        tail_lines: if synced { None } else { params.tail_lines },
+                        is_multi_container: container.is_none() && total > 1,
+                        ..params
+                    };
+                    let target = ResolvedContainer {
+                        api: api.clone(),
+                        pod_name: pod_name.clone(),
+                        label: pod_name.clone(),
+                        status: container_status(pod, &container_name),
+                        container_name,
+                    };
+                    let sender = log_sender.clone();
+                    let attached = attached.clone();
+                    let _guard = task_handle.guard();
+                    let task_handle = task_handle.clone();
+                    let options = options.clone();
+
+                    handle.spawn(async move {
+                        let _guard = _guard;
+                        stream_container_logs(
+                            target,
+                            &sender,
+                            &task_handle,
+                            params,
+                            &token,
+                            options,
+                        )
+                        .await;
+                        if let Ok(mut map) = attached.lock() {
+                            map.remove(&key);
+                        }
+                        let _ = sender.send(format!("- {key}").into());
+                    });
+                }
+            }
+            synced = true;
+
+            let event = tokio::select! {
+                _ = cancel.cancelled() => break,
+                event = pods.next() => event,
+            };
+            match event {
+                SubscriptionEvent::Changed => {}
+                SubscriptionEvent::Status(status) => {
+                    if let Some(message) = &status.last_error {
+                        if last_error.as_ref() != Some(message) {
+                            tracing::warn!("log follower watch error for {selector}: {message}");
+                            let line = format!("[{selector}] watch error: {message}");
+                            let _ = log_sender.send(line.into());
+                        }
+                    }
+                    last_error = status.last_error;
+                }
+                // Evicted or restarted with a new client: follow its replacement
+                SubscriptionEvent::Stopped => tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    subscription = follow.subscribe() => match subscription {
+                        Ok(subscription) => pods = subscription,
+                        Err(e) => {
+                            let line = format!("[{selector}] watch stopped: {e}");
+                            let _ = log_sender.send(line.into());
+                            break;
+                        }
+                    },
+                },
+            }
+        }
+    });
+}
+
 // ============================================================================
 // Histogram Rendering
 // ============================================================================
@@ -150,7 +670,7 @@ const HISTOGRAM_BAR_CHARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅',
 const DEFAULT_HISTOGRAM_BUCKETS: usize = 50;
 
 /// Try to find and parse a K8s timestamp (2024-01-15T10:30:45.123Z format)
-fn find_timestamp(line: &str) -> Option<Timestamp> {
+pub(crate) fn find_timestamp(line: &str) -> Option<Timestamp> {
     for (i, _) in line.match_indices('T') {
         if i < 10 || i + 9 > line.len() {
             continue;
@@ -168,6 +688,46 @@ fn find_timestamp(line: &str) -> Option<Timestamp> {
     None
 }
 
+/// K-way merge of per-container lines, each list already in time order, into one
+/// timeline. Lines without a timestamp sort with the line before them so that
+/// multi-line entries stay together; ties keep container order.
+fn merge_by_timestamp<T>(
+    streams: Vec<Vec<(Option<Timestamp>, T)>>,
+) -> Vec<(Option<Timestamp>, T)> {
+    let total = streams.iter().map(Vec::len).sum();
+    let mut streams: Vec<_> = streams.into_iter().map(|s| s.into_iter().peekable()).collect();
+    let mut last_seen = vec![Timestamp::MIN; streams.len()];
+    let mut heap = BinaryHeap::new();
+
+    for (i, stream) in streams.iter_mut().enumerate() {
+        if let Some((ts, _)) = stream.peek() {
+            heap.push(Reverse((ts.unwrap_or(Timestamp::MIN), i)));
+        }
+    }
+
+    let mut merged = Vec::with_capacity(total);
+    while let Some(Reverse((_, i))) = heap.pop() {
+        let Some(item) = streams[i].next() else {
+            continue;
+        };
+        if let Some(ts) = item.0 {
+            last_seen[i] = ts;
+        }
+        merged.push(item);
+        if let Some((ts, _)) = streams[i].peek() {
+            heap.push(Reverse((ts.unwrap_or(last_seen[i]), i)));
+        }
+    }
+    merged
+}
+
+/// Remove the timestamp the API adds with `timestamps: true`, which follows the prefix.
+pub(crate) fn strip_line_timestamp(line: &mut LogLine) {
+    let body = &line.text[line.prefix_len..];
+    let timestamp_len = body.len() - skip_timestamp(body).len();
+    line.text.replace_range(line.prefix_len..line.prefix_len + timestamp_len, "");
+}
+
 /// Format a timestamp label based on the time range duration.
 fn format_time_label(ts: Timestamp, total_hours: i64) -> String {
     let zdt = ts.to_zoned(jiff::tz::TimeZone::UTC);
@@ -180,69 +740,252 @@ fn format_time_label(ts: Timestamp, total_hours: i64) -> String {
     }
 }
 
-/// Build and render histogram as bar chart lines.
 /// Maximum number of histogram buckets to prevent memory issues with very wide terminals
 const MAX_HISTOGRAM_BUCKETS: usize = 500;
 
-fn render_histogram(
-    lines: &[String],
+/// Pods beyond this many get folded into an "other" row.
+const MAX_HISTOGRAM_PODS: usize = 8;
+
+/// How histogram rows are split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HistogramSplit {
+    Total,
+    Level,
+    Pod,
+}
+
+impl HistogramSplit {
+    fn parse(s: Option<&str>) -> Self {
+        match s {
+            Some("level") => HistogramSplit::Level,
+            Some("pod") => HistogramSplit::Pod,
+            _ => HistogramSplit::Total,
+        }
+    }
+}
+
+/// Line counts over time, one row per series.
+#[derive(Debug, Serialize)]
+struct Histogram {
+    /// RFC 3339 bounds of the whole window
+    start: String,
+    end: String,
+    bucket_seconds: f64,
+    series: Vec<String>,
+    buckets: Vec<HistogramBucket>,
+    /// Display column of the first bar in every row
+    bar_col: usize,
+    /// Rendered lines at the top of the output (one per series plus the time axis)
+    rows: usize,
+    #[serde(skip)]
+    start_time: Timestamp,
+    #[serde(skip)]
+    end_time: Timestamp,
+}
+
+#[derive(Debug, Serialize)]
+struct HistogramBucket {
+    start: String,
+    end: String,
+    /// Lines per series, parallel to `Histogram::series`
+    counts: Vec<usize>,
+    /// 1-based index of the first log line in this bucket
+    first_line: Option<usize>,
+}
+
+/// A timestamped line as `(timestamp, series index, 1-based line index)`.
+type HistogramSample = (Timestamp, usize, usize);
+
+/// Series names and samples for `split`. Lines without a timestamp are skipped,
+/// as are status lines when splitting by pod.
+fn histogram_samples(
+    lines: &[(Option<Timestamp>, LogLine)],
+    split: HistogramSplit,
+) -> (Vec<String>, Vec<HistogramSample>) {
+    let timed = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (ts, line))| ts.map(|ts| (ts, line, i + 1)));
+
+    match split {
+        HistogramSplit::Total => {
+            let samples = timed.map(|(ts, _, i)| (ts, 0, i)).collect();
+            (vec!["total".to_owned()], samples)
+        }
+        HistogramSplit::Level => {
+            let series = ["error", "warn", "info", "other"].map(str::to_owned).to_vec();
+            let samples = timed
+                .map(|(ts, line, i)| {
+                    let row = match detect_level(&line.text[line.prefix_len..]) {
+                        Some(LogLevel::Error | LogLevel::Fatal) => 0,
+                        Some(LogLevel::Warn) => 1,
+                        Some(LogLevel::Info) => 2,
+                        _ => 3,
+                    };
+                    (ts, row, i)
+                })
+                .collect();
+            (series, samples)
+        }
+        HistogramSplit::Pod => {
+            let timed: Vec<_> = timed
+                .filter_map(|(ts, line, i)| line.source.as_ref().map(|s| (ts, s.pod.as_str(), i)))
+                .collect();
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for (_, pod, _) in &timed {
+                *counts.entry(pod).or_default() += 1;
+            }
+            let mut pods: Vec<&str> = counts.keys().copied().collect();
+            pods.sort_by(|a, b| counts[b].cmp(&counts[a]).then(a.cmp(b)));
+            let folded = pods.len() > MAX_HISTOGRAM_PODS;
+            pods.truncate(if folded { MAX_HISTOGRAM_PODS - 1 } else { MAX_HISTOGRAM_PODS });
+            pods.sort_unstable();
+
+            let samples = timed
+                .iter()
+                .map(|(ts, pod, i)| {
+                    let row = pods.iter().position(|p| p == pod).unwrap_or(pods.len());
+                    (*ts, row, *i)
+                })
+                .collect();
+            let mut series: Vec<String> = pods.iter().map(|p| p.to_string()).collect();
+            if folded {
+                series.push("other".to_owned());
+            }
+            (series, samples)
+        }
+    }
+}
+
+/// Bucket samples between `now - since_span` (or the oldest sample) and `now`.
+/// Split histograms give up some buckets to a series label column so rows keep
+/// the requested width. Rows without samples are dropped.
+fn build_histogram(
+    mut series: Vec<String>,
+    samples: &[HistogramSample],
     since_span: Option<Span>,
-    bucket_count: usize,
-) -> Vec<String> {
-    if bucket_count < 12 || bucket_count > MAX_HISTOGRAM_BUCKETS {
-        return Vec::new();
+    width: usize,
+    now: Timestamp,
+) -> Option<Histogram> {
+    if width > MAX_HISTOGRAM_BUCKETS || samples.is_empty() {
+        return None;
     }
 
-    let timestamps: Vec<Timestamp> = lines.iter().filter_map(|l| find_timestamp(l)).collect();
-    if timestamps.is_empty() {
-        return Vec::new();
+    let mut totals = vec![0usize; series.len()];
+    for (_, row, _) in samples {
+        totals[*row] += 1;
     }
+    let keep: Vec<usize> = (0..series.len()).filter(|r| totals[*r] > 0).collect();
+    let remap: HashMap<usize, usize> =
+        keep.iter().enumerate().map(|(new, old)| (*old, new)).collect();
+    series = keep.iter().map(|r| series[*r].clone()).collect();
 
-    let now = Timestamp::now();
-    let start_time = match since_span
-        .and_then(|span| now.checked_sub(span).ok())
-        .or_else(|| timestamps.iter().min().copied())
-    {
-        Some(t) => t,
-        None => return Vec::new(),
+    let label_width = if series.len() > 1 {
+        series.iter().map(|s| s.chars().count()).max().unwrap_or(0) + 1
+    } else {
+        0
     };
-    let end_time = now;
+    let bucket_count = width.saturating_sub(label_width);
+    if bucket_count < 12 {
+        return None;
+    }
 
-    let total_duration = end_time.duration_since(start_time);
-    let total_secs = total_duration.as_secs();
+    let start_time = since_span
+        .and_then(|span| now.checked_sub(span).ok())
+        .or_else(|| samples.iter().map(|(ts, _, _)| *ts).min())?;
+    let total_secs = now.duration_since(start_time).as_secs();
     if total_secs <= 0 {
-        return Vec::new();
+        return None;
     }
+    let bucket_seconds = total_secs as f64 / bucket_count as f64;
 
-    let bucket_duration_secs = total_secs as f64 / bucket_count as f64;
-
-    let mut buckets = vec![0usize; bucket_count];
-    for ts in &timestamps {
+    let mut counts = vec![vec![0usize; series.len()]; bucket_count];
+    let mut first_lines = vec![None; bucket_count];
+    for (ts, row, line) in samples {
         let offset = ts.duration_since(start_time).as_secs() as f64;
-        let bucket_idx = ((offset / bucket_duration_secs) as usize).min(bucket_count - 1);
-        buckets[bucket_idx] += 1;
+        let idx = ((offset.max(0.0) / bucket_seconds) as usize).min(bucket_count - 1);
+        counts[idx][remap[row]] += 1;
+        first_lines[idx] = Some(first_lines[idx].map_or(*line, |f: usize| f.min(*line)));
     }
 
-    let max_count = *buckets.iter().max().unwrap_or(&1).max(&1);
+    let bucket_bound = |i: usize| {
+        let secs = (i as f64 * bucket_seconds) as i64;
+        start_time
+            .checked_add(Span::new().seconds(secs))
+            .unwrap_or(now)
+            .to_string()
+    };
+    let buckets = counts
+        .into_iter()
+        .zip(first_lines)
+        .enumerate()
+        .map(|(i, (counts, first_line))| HistogramBucket {
+            start: bucket_bound(i),
+            end: bucket_bound(i + 1),
+            counts,
+            first_line,
+        })
+        .collect();
+
+    Some(Histogram {
+        start: start_time.to_string(),
+        end: now.to_string(),
+        bucket_seconds,
+        rows: series.len() + 1,
+        series,
+        buckets,
+        bar_col: label_width + 1,
+        start_time,
+        end_time: now,
+    })
+}
 
-    // Build bar line
-    let mut bar_line = String::from("│");
-    for count in &buckets {
-        let level = ((*count as f64 / max_count as f64) * 8.0 + 0.5) as usize;
-        bar_line.push(HISTOGRAM_BAR_CHARS[level.min(8)]);
+/// Render one bar row per series, each scaled to its own peak so that small
+/// series such as errors stay visible, followed by a time axis.
+fn render_histogram(histogram: &Histogram) -> Vec<String> {
+    let label_width = histogram.bar_col - 1;
+    let mut lines = Vec::with_capacity(histogram.rows);
+
+    for (row, name) in histogram.series.iter().enumerate() {
+        let max_count = histogram
+            .buckets
+            .iter()
+            .map(|b| b.counts[row])
+            .max()
+            .unwrap_or(1)
+            .max(1);
+
+        let mut bar_line = if label_width > 0 {
+            format!("{name:<width$}", width = label_width)
+        } else {
+            String::new()
+        };
+        bar_line.push('│');
+        for bucket in &histogram.buckets {
+            let level = ((bucket.counts[row] as f64 / max_count as f64) * 8.0 + 0.5) as usize;
+            bar_line.push(HISTOGRAM_BAR_CHARS[level.min(8)]);
+        }
+        bar_line.push('│');
+        lines.push(bar_line);
     }
-    bar_line.push('│');
 
-    // Build label line
-    let total_hours = (total_secs / 3600) as i64;
-    let first_label = format_time_label(start_time, total_hours);
-    let last_label = format_time_label(end_time, total_hours);
+    let bucket_count = histogram.buckets.len();
+    let total_secs = histogram.end_time.duration_since(histogram.start_time).as_secs();
+    let total_hours = total_secs / 3600;
+    let first_label = format_time_label(histogram.start_time, total_hours);
+    let last_label = format_time_label(histogram.end_time, total_hours);
     let padding = bucket_count
         .saturating_sub(first_label.len() + last_label.len())
         .max(1);
-    let label_line = format!(" {}{}{}", first_label, "─".repeat(padding), last_label);
-
-    vec![bar_line, label_line]
+    lines.push(format!(
+        "{} {}{}{}",
+        " ".repeat(label_width),
+        first_label,
+        "─".repeat(padding),
+        last_label
+    ));
+
+    lines
 }
 
 // ============================================================================
@@ -254,31 +997,27 @@ const MAX_CHUNK_SIZE: usize = 100;
 
 /// A streaming log session that follows pod logs in real-time.
 pub struct LogSession {
-    session: StreamingSession<String>,
+    session: StreamingSession<LogLine>,
+    /// Stops every stream and the pod follower, even while they wait for output
+    cancel: CancellationToken,
+    /// Lines delivered to Lua, for `log_search_async` and `context`
+    buffer: Arc<Mutex<LogBuffer>>,
+    /// Only follow sessions keep their lines; one-shot output is searched with `/` in Neovim
+    buffered: bool,
+    /// Key of `buffer` for `log_search_async`
+    buffer_id: usize,
 }
 
 impl LogSession {
     #[tracing::instrument(skip(client))]
     pub fn new(client: Client, config: LogConfig) -> LuaResult<Self> {
-        let targets = block_on(async {
-            resolve_log_targets(
-                &client,
-                &config.pods,
-                config.container.as_deref(),
-                config.prefix,
-            )
-            .await
-        })
-        .map_err(LuaError::external)?;
-
-        if targets.containers.is_empty() {
-            return Err(LuaError::external("No containers found"));
-        }
-
         let session = StreamingSession::new();
+        let cancel = CancellationToken::new();
+        let options = Arc::new(LineOptions::new(&config).map_err(LuaError::external)?);
         let runtime = RUNTIME
             .get()
             .ok_or_else(|| LuaError::runtime("Tokio runtime not initialized"))?;
+        let buffer_lines = config.buffer_lines.unwrap_or(DEFAULT_BUFFER_LINES);
 
         let since_time = config
             .since
@@ -286,24 +1025,67 @@ impl LogSession {
             .and_then(parse_duration)
             .and_then(|span| Timestamp::now().checked_sub(span).ok());
 
-        // If following with no since, use since_seconds=1 to start from "now"
+        // If following with no since or tail, use since_seconds=1 to start from "now"
         let follow = config.follow.unwrap_or(false);
-        let since_seconds = if follow && since_time.is_none() {
+        let since_seconds = if follow && since_time.is_none() && config.tail_lines.is_none() {
             Some(1)
         } else {
             None
         };
 
-        let params = ContainerLogParams {
+        let mut params = ContainerLogParams {
             follow,
             since_time,
             since_seconds,
+            tail_lines: config.tail_lines,
+            limit_bytes: config.limit_bytes,
             timestamps: config.timestamps.unwrap_or(false),
             previous: config.previous.unwrap_or(false),
-            use_prefix: targets.use_prefix,
-            is_multi_container: targets.is_multi_container,
+            use_prefix: config.prefix.unwrap_or(true),
+            is_multi_container: false,
         };
 
+        let pods = match &config.selector {
+            // Job runs are resolved up front so finished pods are included
+            Some(target) if follow && !is_job_target(target) => {
+                let selector = block_on(resolve_pod_selector(&client, target))
+                    .map_err(LuaError::external)?;
+                let follow = FollowTarget {
+                    context: crate::active_context(),
+                    namespace: target.namespace.clone(),
+                    selector,
+                    container: config.container.clone(),
+                    options,
+                };
+                spawn_pod_follower(
+                    runtime,
+                    client,
+                    follow,
+                    session.sender(),
+                    session.task_handle(),
+                    cancel.clone(),
+                    params,
+                );
+                return Ok(Self::with_buffer(session, cancel, buffer_lines, true));
+            }
+            Some(target) => {
+                block_on(list_selected_pods(&client, target)).map_err(LuaError::external)?
+            }
+            None => config.pods.clone(),
+        };
+
+        let targets = block_on(async {
+            resolve_log_targets(&client, &pods, config.container.as_deref(), config.prefix).await
+        })
+        .map_err(LuaError::external)?;
+
+        if targets.containers.is_empty() {
+            return Err(LuaError::external("No containers found"));
+        }
+
+        params.use_prefix = targets.use_prefix;
+        params.is_multi_container = targets.is_multi_container;
+
         for target in targets.containers {
             spawn_container_log_task(
                 runtime,
@@ -311,30 +1093,130 @@ impl LogSession {
                 session.sender(),
                 session.task_handle(),
                 params,
+                cancel.child_token(),
+                options.clone(),
             );
         }
 
-        Ok(LogSession { session })
+        Ok(Self::with_buffer(session, cancel, buffer_lines, follow))
     }
 
-    fn read_chunk(&self) -> LuaResult<Option<Vec<String>>> {
-        let lines = self
+    fn with_buffer(
+        session: StreamingSession<LogLine>,
+        cancel: CancellationToken,
+        buffer_lines: usize,
+        buffered: bool,
+    ) -> Self {
+        let capacity = if buffered { buffer_lines } else { 0 };
+        let buffer = Arc::new(Mutex::new(LogBuffer::new(capacity)));
+        let buffer_id = log_index::register(buffer.clone());
+        LogSession {
+            session,
+            cancel,
+            buffer,
+            buffered,
+            buffer_id,
+        }
+    }
+
+    /// Take the next chunk for Lua and, when following, remember it for searching.
+    fn recv_chunk(&self) -> LuaResult<Vec<LogLine>> {
+        let chunk = self
             .session
             .try_recv_batch(MAX_CHUNK_SIZE)
             .map_err(|e| LuaError::runtime(e.to_string()))?;
 
+        if self.buffered && !chunk.is_empty() {
+            let mut buffer = self
+                .buffer
+                .lock()
+                .map_err(|_| LuaError::runtime("poisoned log buffer lock"))?;
+            for line in &chunk {
+                buffer.push(&line.text);
+            }
+        }
+        Ok(chunk)
+    }
+
+    /// Retained lines within `n` of `line`, as `{ { line, text }, ... }`.
+    fn context(&self, lua: &Lua, line: usize, n: usize) -> LuaResult<LuaValue> {
+        let context = self
+            .buffer
+            .lock()
+            .map_err(|_| LuaError::runtime("poisoned log buffer lock"))?
+            .context(line, n);
+        lua.to_value(&context)
+    }
+
+    fn read_chunk(&self) -> LuaResult<Option<Vec<String>>> {
+        let lines = self.recv_chunk()?;
+
         if lines.is_empty() {
             Ok(None)
         } else {
-            Ok(Some(lines))
+            Ok(Some(lines.into_iter().map(|l| l.text).collect()))
         }
     }
 
-    fn is_open(&self) -> bool {
+    /// Like `read_chunk`, but returns `{ lines, spans }` where `spans[i]` lists the
+    /// 0-based, end-exclusive byte ranges of include matches on line `i`.
+    fn read_chunk_with_spans(&self, lua: &Lua) -> LuaResult<Option<LuaTable>> {
+        let chunk = self.recv_chunk()?;
+
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        let lines = lua.create_table()?;
+        let spans = lua.create_table()?;
+        for line in chunk {
+            let line_spans = lua.create_table()?;
+            for (start, end) in line.spans {
+                line_spans.push(lua.create_sequence_from([start, end])?)?;
+            }
+            lines.push(line.text)?;
+            spans.push(line_spans)?;
+        }
+
+        let result = lua.create_table()?;
+        result.set("lines", lines)?;
+        result.set("spans", spans)?;
+        Ok(Some(result))
+    }
+
+    /// One table per line: `{ text, prefix, spans, format?, timestamp?, level?, msg?, fields? }`.
+    /// Record keys are only set for lines parsed as JSON or logfmt.
+    fn read_records(&self, lua: &Lua) -> LuaResult<Option<LuaValue>> {
+        let chunk = self.recv_chunk()?;
+
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        let records: Vec<LineRecord> = chunk
+            .iter()
+            .map(|line| LineRecord {
+                text: &line.text,
+                prefix: &line.text[..line.prefix_len],
+                spans: &line.spans,
+                record: line.record.as_ref(),
+            })
+            .collect();
+        let options = LuaSerializeOptions::new().serialize_none_to_null(false);
+        lua.to_value_with(&records, options).map(Some)
+    }
+
+    /// Take up to `limit` pending lines, for consumers other than Lua.
+    pub(crate) fn take_lines(&self, limit: usize) -> Result<Vec<LogLine>, String> {
+        self.session.try_recv_batch(limit).map_err(|e| e.to_string())
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
         self.session.is_open()
     }
 
-    fn close(&self) {
+    pub(crate) fn close(&self) {
+        self.cancel.cancel();
         self.session.close();
     }
 }
@@ -342,6 +1224,14 @@ impl LogSession {
 impl UserData for LogSession {
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_method("read_chunk", |_, this, ()| this.read_chunk());
+        methods.add_method("read_chunk_with_spans", |lua, this, ()| {
+            this.read_chunk_with_spans(lua)
+        });
+        methods.add_method("read_records", |lua, this, ()| this.read_records(lua));
+        methods.add_method("context", |lua, this, (line, n): (usize, Option<usize>)| {
+            this.context(lua, line, n.unwrap_or(5))
+        });
+        methods.add_method("id", |_, this, ()| Ok(this.buffer_id));
         methods.add_method("open", |_, this, ()| Ok(this.is_open()));
         methods.add_method("close", |_, this, ()| {
             this.close();
@@ -350,12 +1240,20 @@ impl UserData for LogSession {
     }
 }
 
+impl Drop for LogSession {
+    fn drop(&mut self) {
+        log_index::unregister(self.buffer_id);
+    }
+}
+
 /// Parameters for a container log streaming task.
 #[derive(Clone, Copy)]
 struct ContainerLogParams {
     follow: bool,
     since_time: Option<Timestamp>,
     since_seconds: Option<i64>,
+    tail_lines: Option<i64>,
+    limit_bytes: Option<i64>,
     timestamps: bool,
     previous: bool,
     use_prefix: bool,
@@ -366,9 +1264,11 @@ struct ContainerLogParams {
 fn spawn_container_log_task(
     runtime: &tokio::runtime::Runtime,
     target: ResolvedContainer,
-    log_sender: mpsc::UnboundedSender<String>,
+    log_sender: mpsc::UnboundedSender<LogLine>,
     task_handle: TaskHandle,
     params: ContainerLogParams,
+    cancel: CancellationToken,
+    options: Arc<LineOptions>,
 ) {
     // Guard automatically decrements task count when dropped
     let _guard = task_handle.guard();
@@ -376,52 +1276,228 @@ fn spawn_container_log_task(
     runtime.spawn(async move {
         // Move guard into async block so it's dropped when task completes
         let _guard = _guard;
+        stream_container_logs(target, &log_sender, &task_handle, params, &cancel, options).await;
+    });
+}
+
+/// Delays before reopening a follow stream, indexed by consecutive failures.
+const RECONNECT_BACKOFF_SECS: [u64; 6] = [0, 1, 2, 5, 10, 30];
+
+/// Consecutive reconnects without new output before a follow gives up.
+const MAX_RECONNECTS: usize = 10;
+
+/// Position of a follow stream, so that a reconnect with `since_time` skips lines
+/// that were already delivered. The API applies `sinceTime` with second
+/// precision, so the replay can start slightly before the last line.
+#[derive(Debug, Default)]
+struct ResumePoint {
+    last: Option<Timestamp>,
+    /// Lines delivered with exactly `last`
+    seen_at_last: usize,
+    /// Lines at `last` still to skip in the replay after a reconnect
+    replay_skip: usize,
+}
 
+impl ResumePoint {
+    /// Record a line; returns false when it was already delivered.
+    fn advance(&mut self, ts: Timestamp) -> bool {
+        match self.last {
+            Some(last) if ts < last => false,
+            Some(last) if ts == last && self.replay_skip > 0 => {
+                self.replay_skip -= 1;
+                false
+            }
+            Some(last) if ts == last => {
+                self.seen_at_last += 1;
+                true
+            }
+            _ => {
+                self.last = Some(ts);
+                self.seen_at_last = 1;
+                self.replay_skip = 0;
+                true
+            }
+        }
+    }
+
+    /// Prepare for a reconnect that replays from `last`.
+    fn rewind(&mut self) {
+        self.replay_skip = self.seen_at_last;
+    }
+}
+
+/// Split the leading `timestamps: true` timestamp off a raw line.
+fn split_timestamp(line: &str) -> (Option<Timestamp>, &str) {
+    match line.split_once(' ') {
+        Some((first, rest)) => match first.parse::<Timestamp>() {
+            Ok(ts) => (Some(ts), rest),
+            Err(_) => (None, line),
+        },
+        None => (None, line),
+    }
+}
+
+/// How a single log stream ended.
+enum StreamEnd {
+    /// Session closed, cancelled or the receiver went away
+    Stopped,
+    Closed,
+    Failed(String),
+}
+
+/// Stream one container's log into `log_sender` until it ends, errors or is cancelled.
+async fn stream_container_logs(
+    target: ResolvedContainer,
+    log_sender: &mpsc::UnboundedSender<LogLine>,
+    task_handle: &TaskHandle,
+    params: ContainerLogParams,
+    cancel: &CancellationToken,
+    options: Arc<LineOptions>,
+) {
+    // A byte-limited stream ends on purpose, so only unlimited follows reconnect.
+    // They always request timestamps to know where to resume.
+    let resumable = params.follow && params.limit_bytes.is_none();
+    let source = Arc::new(LogSource {
+        pod: target.pod_name.clone(),
+        container: target.container_name.clone(),
+    });
+    let mut resume = ResumePoint::default();
+    let mut failures = 0;
+
+    loop {
+        let resuming = resume.last.is_some();
         let log_params = LogParams {
             follow: params.follow,
             container: Some(target.container_name.clone()),
-            since_time: params.since_time,
-            since_seconds: params.since_seconds,
-            timestamps: params.timestamps,
+            since_time: resume.last.or(params.since_time),
+            since_seconds: if resuming { None } else { params.since_seconds },
+            tail_lines: if resuming { None } else { params.tail_lines },
+            limit_bytes: params.limit_bytes,
+            timestamps: params.timestamps || resumable,
             previous: params.previous,
             ..LogParams::default()
         };
 
-        let log_stream = match target.api.log_stream(&target.pod_name, &log_params).await {
-            Ok(stream) => stream,
-            Err(e) => {
-                let _ = log_sender.send(format!("[{}] Error: {}", target.pod_name, e));
-                return;
-            }
-        };
+        let mut delivered = false;
+        let end = match target.api.log_stream(&target.pod_name, &log_params).await {
+            Ok(log_stream) => {
+                let mut lines = log_stream.lines();
+                loop {
+                    if !task_handle.is_active() {
+                        break StreamEnd::Stopped;
+                    }
 
-        let mut lines = log_stream.lines();
-        loop {
-            if !task_handle.is_active() {
-                break;
-            }
+                    let next = tokio::select! {
+                        _ = cancel.cancelled() => break StreamEnd::Stopped,
+                        next = lines.try_next() => next,
+                    };
+
+                    let raw = match next {
+                        Ok(Some(raw)) => raw,
+                        Ok(None) => break StreamEnd::Closed,
+                        Err(e) => break StreamEnd::Failed(e.to_string()),
+                    };
+
+                    let mut line = raw.as_str();
+                    if resumable {
+                        let (timestamp, body) = split_timestamp(&raw);
+                        if timestamp.is_some_and(|ts| !resume.advance(ts)) {
+                            continue;
+                        }
+                        if !params.timestamps {
+                            line = body;
+                        }
+                    }
+                    delivered = true;
 
-            match lines.try_next().await {
-                Ok(Some(line)) => {
-                    let formatted = format_log_line(
-                        &line,
-                        &target.pod_name,
+                    let Some(mut formatted) = build_log_line(
+                        line,
+                        &target.label,
                         &target.container_name,
                         params.use_prefix,
                         params.is_multi_container,
-                    );
+                        &options,
+                    ) else {
+                        continue;
+                    };
+                    formatted.source = Some(source.clone());
                     if log_sender.send(formatted).is_err() {
-                        break;
+                        break StreamEnd::Stopped;
                     }
                 }
-                Ok(None) => break,
-                Err(e) => {
-                    let _ = log_sender.send(format!("[{}] Stream error: {}", target.pod_name, e));
-                    break;
-                }
             }
+            Err(e) if failures == 0 && !resuming => {
+                let _ = log_sender.send(format!("[{}] Error: {}", target.pod_name, e).into());
+                return;
+            }
+            Err(e) => StreamEnd::Failed(e.to_string()),
+        };
+
+        let error = match end {
+            StreamEnd::Stopped => return,
+            StreamEnd::Closed => None,
+            StreamEnd::Failed(e) => Some(e),
+        };
+        let stop = |reason: String| {
+            let msg = format!("[{}] follow stopped: {}", target.pod_name, reason);
+            let _ = log_sender.send(msg.into());
+        };
+
+        if !resumable {
+            if let Some(e) = error {
+                let msg = format!("[{}] Stream error: {}", target.pod_name, e);
+                let _ = log_sender.send(msg.into());
+            }
+            return;
         }
-    });
+
+        // A clean close of a still-running container (e.g. a kubelet timeout) is not a failure
+        match (&error, delivered) {
+            (_, true) => failures = 0,
+            (Some(_), false) => failures += 1,
+            (None, false) => {}
+        }
+        if failures > MAX_RECONNECTS {
+            let e = error.unwrap_or_default();
+            stop(format!("{MAX_RECONNECTS} reconnects without output, last error: {e}"));
+            return;
+        }
+        if !container_running(&target).await {
+            let reason = match error {
+                Some(e) => format!("container {} stopped: {e}", target.container_name),
+                None => format!("container {} is not running", target.container_name),
+            };
+            stop(reason);
+            return;
+        }
+        if let Some(e) = &error {
+            let (pod, container) = (&target.pod_name, &target.container_name);
+            tracing::warn!("{pod}/{container}: log stream failed, reconnecting: {e}");
+        }
+
+        let delay = RECONNECT_BACKOFF_SECS[failures.min(RECONNECT_BACKOFF_SECS.len() - 1)];
+        // Quiet clean closes don't add to the backoff, but must not reopen in a tight loop
+        let delay = if error.is_none() && !delivered {
+            delay.max(1)
+        } else {
+            delay
+        };
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tokio::time::sleep(std::time::Duration::from_secs(delay)) => {}
+        }
+        resume.rewind();
+    }
+}
+
+/// Whether a followed container still runs, so that its ended stream should be reopened.
+/// Lookup errors other than a deleted pod count as running; reconnects are bounded.
+async fn container_running(target: &ResolvedContainer) -> bool {
+    match target.api.get(&target.pod_name).await {
+        Ok(pod) => !running_containers(&pod, Some(&target.container_name)).is_empty(),
+        Err(kube::Error::Api(s)) if s.code == 404 => false,
+        Err(_) => true,
+    }
 }
 
 // ============================================================================
@@ -447,10 +1523,20 @@ pub async fn fetch_logs_async(_lua: mlua::Lua, json: String) -> mlua::Result<Str
         .as_ref()
         .and_then(|span| Timestamp::now().checked_sub(*span).ok());
 
+    let ordered = config.ordered.unwrap_or(false);
+
     with_client(move |client| async move {
+        let options = Arc::new(LineOptions::new(&config).map_err(mlua::Error::external)?);
+        let pods = match &config.selector {
+            Some(target) => list_selected_pods(&client, target)
+                .await
+                .map_err(mlua::Error::external)?,
+            None => config.pods.clone(),
+        };
+
         let targets = resolve_log_targets(
             &client,
-            &config.pods,
+            &pods,
             config.container.as_deref(),
             config.prefix,
         )
@@ -469,8 +1555,10 @@ pub async fn fetch_logs_async(_lua: mlua::Lua, json: String) -> mlua::Result<Str
                 follow: false,
                 container: Some(target.container_name.clone()),
                 since_time,
+                tail_lines: config.tail_lines,
+                limit_bytes: config.limit_bytes,
                 pretty: true,
-                timestamps: config.timestamps.unwrap_or(false),
+                timestamps: config.timestamps.unwrap_or(false) || ordered,
                 previous: config.previous.unwrap_or(false),
                 ..LogParams::default()
             };
@@ -480,13 +1568,30 @@ pub async fn fetch_logs_async(_lua: mlua::Lua, json: String) -> mlua::Result<Str
                 Err(_) => continue,
             };
 
-            let pod_name = target.pod_name;
+            let label = target.label;
             let container_name = target.container_name;
             let use_prefix = targets.use_prefix;
             let is_multi_container = targets.is_multi_container;
+            let options = options.clone();
+            let source = Arc::new(LogSource {
+                pod: target.pod_name,
+                container: container_name.clone(),
+            });
 
-            let stream = log_stream.lines().map_ok(move |line| {
-                format_log_line(&line, &pod_name, &container_name, use_prefix, is_multi_container)
+            let stream = log_stream.lines().try_filter_map(move |raw| {
+                let line = build_log_line(
+                    &raw,
+                    &label,
+                    &container_name,
+                    use_prefix,
+                    is_multi_container,
+                    &options,
+                )
+                .map(|mut line| {
+                    line.source = Some(source.clone());
+                    (find_timestamp(&raw), line)
+                });
+                futures::future::ready(Ok(line))
             });
             all_streams.push(stream);
         }
@@ -496,16 +1601,37 @@ pub async fn fetch_logs_async(_lua: mlua::Lua, json: String) -> mlua::Result<Str
                 .map_err(|e| mlua::Error::external(format!("json encode error: {e}")));
         }
 
-        let mut combined = futures::stream::select_all(all_streams);
-        let mut collected_logs: Vec<String> = Vec::new();
+        let collected_logs = if ordered {
+            let streams = futures::future::try_join_all(
+                all_streams.into_iter().map(|stream| stream.try_collect::<Vec<_>>()),
+            )
+            .await?;
+            merge_by_timestamp(streams)
+        } else {
+            futures::stream::select_all(all_streams).try_collect().await?
+        };
 
-        while let Some(line) = combined.try_next().await? {
-            collected_logs.push(line);
+        let split = HistogramSplit::parse(config.histogram_split.as_deref());
+        let (series, samples) = histogram_samples(&collected_logs, split);
+        let histogram =
+            build_histogram(series, &samples, since_span, bucket_count, Timestamp::now());
+        let mut result = histogram.as_ref().map(render_histogram).unwrap_or_default();
+        let strip = ordered && !config.timestamps.unwrap_or(false);
+        result.extend(collected_logs.into_iter().map(|(_, mut line)| {
+            if strip {
+                strip_line_timestamp(&mut line);
+            }
+            line.text
+        }));
+
+        if config.histogram_data.unwrap_or(false) {
+            return serde_json::to_string(&serde_json::json!({
+                "histogram": histogram,
+                "lines": result,
+            }))
+            .map_err(|e| mlua::Error::external(format!("json encode error: {e}")));
         }
 
-        let mut result = render_histogram(&collected_logs, since_span, bucket_count);
-        result.append(&mut collected_logs);
-
         serde_json::to_string(&result)
             .map_err(|e| mlua::Error::external(format!("json encode error: {e}")))
     })
@@ -565,3 +1691,88 @@ pub fn toggle_json(input: &str) -> Option<ToggleJsonResult> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> Option<Timestamp> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn merge_orders_across_streams_and_keeps_continuations() {
+        let a = vec![
+            (ts("2024-01-15T10:00:00Z"), "a1"),
+            (None, "a1 cont"),
+            (ts("2024-01-15T10:00:03Z"), "a2"),
+        ];
+        let b = vec![
+            (ts("2024-01-15T10:00:01Z"), "b1"),
+            (ts("2024-01-15T10:00:03Z"), "b2"),
+        ];
+        let merged: Vec<_> = merge_by_timestamp(vec![a, b]).into_iter().map(|(_, l)| l).collect();
+        assert_eq!(merged, vec!["a1", "a1 cont", "b1", "a2", "b2"]);
+    }
+
+    #[test]
+    fn histogram_splits_by_level_and_drops_empty_rows() {
+        let line = |text: &str| LogLine {
+            text: text.to_owned(),
+            ..LogLine::default()
+        };
+        let lines = vec![
+            (ts("2024-01-15T10:00:00Z"), line("ERROR boom")),
+            (ts("2024-01-15T10:00:10Z"), line("INFO ok")),
+            (None, line("  at main.go:1")),
+            (ts("2024-01-15T10:00:59Z"), line("ERROR again")),
+        ];
+        let (series, samples) = histogram_samples(&lines, HistogramSplit::Level);
+        let now = ts("2024-01-15T10:01:00Z").unwrap();
+        let histogram = build_histogram(series, &samples, None, 26, now).unwrap();
+
+        assert_eq!(histogram.series, vec!["error", "info"]);
+        assert_eq!(histogram.bar_col, 7);
+        assert_eq!(histogram.buckets.len(), 20);
+        assert_eq!(histogram.buckets[0].counts, vec![1, 0]);
+        assert_eq!(histogram.buckets[0].first_line, Some(1));
+        assert_eq!(histogram.buckets[19].first_line, Some(4));
+
+        let rendered = render_histogram(&histogram);
+        assert_eq!(rendered.len(), histogram.rows);
+        assert!(rendered[0].starts_with("error │█"));
+    }
+
+    #[test]
+    fn strips_timestamp_after_prefix() {
+        let mut line = LogLine {
+            text: "[web] 2024-01-15T10:30:45.123456789Z hello".to_owned(),
+            prefix_len: 6,
+            ..LogLine::default()
+        };
+        strip_line_timestamp(&mut line);
+        assert_eq!(line.text, "[web] hello");
+    }
+
+    #[test]
+    fn resume_skips_replayed_lines() {
+        let (t1, t2) = (ts("2024-01-15T10:00:01Z").unwrap(), ts("2024-01-15T10:00:02Z").unwrap());
+        let mut resume = ResumePoint::default();
+        assert!(resume.advance(t1));
+        assert!(resume.advance(t2));
+        assert!(resume.advance(t2));
+
+        // Reconnect with since_time = t2 replays from the start of that second
+        resume.rewind();
+        assert!(!resume.advance(t1));
+        assert!(!resume.advance(t2));
+        assert!(!resume.advance(t2));
+        assert!(resume.advance(t2));
+        assert!(resume.advance(ts("2024-01-15T10:00:03Z").unwrap()));
+
+        let (timestamp, body) = split_timestamp("2024-01-15T10:00:01.5Z hello world");
+        assert!(timestamp.is_some());
+        assert_eq!(body, "hello world");
+        assert_eq!(split_timestamp("hello world"), (None, "hello world"));
+    }
+}
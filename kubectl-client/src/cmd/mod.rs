@@ -15,8 +15,14 @@ use crate::cmd::exec::{
 use crate::cmd::get::{
     get_api_resources_async, get_raw_async, get_server_raw_async, get_single, get_single_async,
 };
+use crate::cmd::log_crash::crash_logs_async;
+use crate::cmd::log_export::{log_export_list, log_export_start, log_export_stop};
+use crate::cmd::log_index::log_search_async;
 use crate::cmd::log_session::{fetch_logs_async, log_session};
-use crate::cmd::portforward::{portforward_list, portforward_start, portforward_stop};
+use crate::cmd::portforward::{
+    portforward_detail, portforward_list, portforward_start, portforward_start_all,
+    portforward_stop,
+};
 use crate::cmd::restart::restart_async;
 use crate::cmd::scale::scale_async;
 use crate::hover::get_hover_async;
@@ -30,15 +36,23 @@ pub mod drift;
 pub mod edit;
 pub mod exec;
 pub mod get;
+pub mod log_crash;
+pub mod log_export;
+pub mod log_filter;
+pub mod log_index;
+pub mod log_parse;
 pub mod log_session;
 pub mod portforward;
+pub mod portforward_stats;
 pub mod restart;
 pub mod scale;
 pub mod utils;
 
 pub fn install(lua: &Lua, exports: &LuaTable) -> LuaResult<()> {
     exports.set("portforward_start", lua.create_function(portforward_start)?)?;
+    exports.set("portforward_start_all", lua.create_function(portforward_start_all)?)?;
     exports.set("portforward_list", lua.create_function(portforward_list)?)?;
+    exports.set("portforward_detail", lua.create_function(portforward_detail)?)?;
     exports.set("portforward_stop", lua.create_function(portforward_stop)?)?;
     exports.set("apply_async", lua.create_async_function(apply_async)?)?;
     exports.set(
@@ -125,7 +139,18 @@ pub fn install(lua: &Lua, exports: &LuaTable) -> LuaResult<()> {
         "log_stream_async",
         lua.create_async_function(fetch_logs_async)?,
     )?;
+    exports.set(
+        "crash_logs_async",
+        lua.create_async_function(crash_logs_async)?,
+    )?;
+    exports.set(
+        "log_search_async",
+        lua.create_async_function(log_search_async)?,
+    )?;
     exports.set("log_session", lua.create_function(log_session)?)?;
+    exports.set("log_export_start", lua.create_function(log_export_start)?)?;
+    exports.set("log_export_list", lua.create_function(log_export_list)?)?;
+    exports.set("log_export_stop", lua.create_function(log_export_stop)?)?;
     exports.set("get_drift", lua.create_function(get_drift)?)?;
     exports.set(
         "get_hover_async",
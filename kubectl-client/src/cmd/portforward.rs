@@ -1,37 +1,85 @@
+use futures::StreamExt;
+use k8s_openapi::api::apps::v1::ReplicaSet;
 use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::api::discovery::v1::EndpointSlice;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use k8s_openapi::serde_json::{self, json};
 use kube::{api::ListParams, Api, Client};
 use mlua::prelude::*;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Mutex, OnceLock};
-use tokio::io::AsyncWriteExt;
-use tokio::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
 use tokio::runtime::Runtime;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 use tokio::time;
-use tracing::{error, warn};
+use tracing::{error, info, warn};
 
-use crate::{CLIENT_INSTANCE, RUNTIME};
+use super::log_session::resolve_pod_selector;
+use super::portforward_stats::PFStats;
+use crate::structs::{
+    LogSelector, PortForwardOptions, PortForwardStartArgs, PortRequest, RemotePort,
+};
+use crate::{active_context, context_client, RUNTIME};
 
 static PF_MAP: OnceLock<Mutex<HashMap<usize, PFData>>> = OnceLock::new();
 static PF_COUNTER: AtomicUsize = AtomicUsize::new(1);
 
+/// Delays between attempts to reach a forward's pod within one connection.
+const RETRY_BACKOFF_MS: [u64; 6] = [0, 250, 500, 1000, 2000, 4000];
+
+/// How often a forward checks that its pod is still there while idle.
+const HEALTH_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Clone, Copy, Debug)]
 pub enum PFType {
     Pod,
     Service,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PFStatus {
+    Active,
+    Reconnecting,
+    Failed,
+}
+
+impl PFStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            PFStatus::Active => "active",
+            PFStatus::Reconnecting => "reconnecting",
+            PFStatus::Failed => "failed",
+        }
+    }
+}
+
 pub struct PFData {
     pub handle: tokio::task::JoinHandle<()>,
     pub cancel: Option<oneshot::Sender<()>>,
     pub pf_type: PFType,
+    /// Context the forward was started in
+    pub context: String,
     pub name: String,
     pub namespace: String,
     pub host: String,
+    pub ports: Vec<ForwardedPort>,
+    pub target: Arc<PFTarget>,
+    pub stats: Arc<PFStats>,
+}
+
+/// A bound port mapping of a forward.
+#[derive(Clone, Debug, Serialize)]
+pub struct ForwardedPort {
+    /// The port actually bound, also when 0 was requested
     pub local_port: u16,
     pub remote_port: u16,
+    /// Port name the remote port was resolved from
+    pub name: Option<String>,
 }
 
 type PFResult<T> = Result<T, String>;
@@ -41,26 +89,72 @@ fn err<E: std::fmt::Display>(e: E) -> String {
     e.to_string()
 }
 
+/// Forward one or more ports of a pod or service. Returns the id and the bound
+/// ports, with named remote ports resolved and local port 0 replaced by a free one.
 pub fn portforward_start(
-    _lua: &Lua,
-    args: (String, String, String, String, u16, u16),
-) -> LuaResult<usize> {
-    let (pf_type_str, name, namespace, bind_host, local_port, remote_port) = args;
-
-    let (client, rt) = {
-        let client = {
-            let g = CLIENT_INSTANCE
-                .lock()
-                .map_err(|_| mlua::Error::RuntimeError("poisoned CLIENT_INSTANCE lock".into()))?;
-            g.as_ref()
-                .ok_or_else(|| mlua::Error::RuntimeError("Client not initialized".into()))?
-                .clone()
-        };
-        let rt = RUNTIME.get_or_init(|| Runtime::new().expect("Failed to create Tokio runtime"));
-        (client, rt)
-    };
+    lua: &Lua,
+    args: (String, String, String, String, Vec<PortRequest>, Option<PortForwardOptions>),
+) -> LuaResult<(usize, LuaValue)> {
+    let (pf_type_str, name, namespace, bind_host, requests, opts) = args;
+    let (id, ports) = start_forward(
+        active_context(),
+        &pf_type_str,
+        name,
+        namespace,
+        bind_host,
+        requests,
+        opts.unwrap_or_default(),
+    )?;
+    Ok((id, lua.to_value(&ports)?))
+}
+
+/// Start the given forwards one after another, for `commands.run_async` so profiles
+/// start off the main thread. Each forward runs in its own context, whichever one is
+/// active. Returns `{ id, ports }` or `{ error }` per forward, in order.
+pub fn portforward_start_all(_lua: &Lua, json: String) -> LuaResult<String> {
+    let forwards: Vec<PortForwardStartArgs> = serde_json::from_str(&json)
+        .map_err(|e| mlua::Error::external(format!("bad json: {e}")))?;
+    let results: Vec<serde_json::Value> = forwards
+        .into_iter()
+        .map(|f| {
+            let opts = PortForwardOptions {
+                round_robin: f.round_robin,
+            };
+            let started = start_forward(
+                f.context,
+                &f.kind,
+                f.name,
+                f.namespace,
+                f.host,
+                f.ports,
+                opts,
+            );
+            match started {
+                Ok((id, ports)) => json!({ "id": id, "ports": ports }),
+                Err(e) => json!({ "error": e.to_string() }),
+            }
+        })
+        .collect();
+    serde_json::to_string(&results).map_err(mlua::Error::external)
+}
+
+fn start_forward(
+    context: String,
+    pf_type_str: &str,
+    name: String,
+    namespace: String,
+    bind_host: String,
+    requests: Vec<PortRequest>,
+    opts: PortForwardOptions,
+) -> LuaResult<(usize, Vec<ForwardedPort>)> {
+    if requests.is_empty() {
+        return Err(mlua::Error::RuntimeError("no ports to forward".into()));
+    }
+
+    let rt = RUNTIME.get_or_init(|| Runtime::new().expect("Failed to create Tokio runtime"));
+    let client = rt.block_on(context_client(&context))?;
 
-    let pf_type = match pf_type_str.as_str() {
+    let pf_type = match pf_type_str {
         "Pod" | "pod" => PFType::Pod,
         "Service" | "service" => PFType::Service,
         other => {
@@ -71,27 +165,48 @@ pub fn portforward_start(
     };
 
     let id = PF_COUNTER.fetch_add(1, Ordering::SeqCst);
-    let bind_addr = format!("{bind_host}:{local_port}");
 
-    let listener = match rt.block_on(async { TcpListener::bind(&bind_addr).await }) {
-        Ok(l) => l,
-        Err(e) => {
-            error!("pf#{id}: bind {bind_addr} failed: {e}");
-            return Err(mlua::Error::RuntimeError(format!(
-                "bind {bind_addr} failed: {e}"
-            )));
-        }
-    };
+    let target = Arc::new(PFTarget::new(client, pf_type, &name, &namespace, opts.round_robin));
+    let remote_ports = rt
+        .block_on(resolve_remote_ports(&target, &requests, id))
+        .map_err(mlua::Error::RuntimeError)?;
+
+    let mut listeners = Vec::new();
+    let mut ports = Vec::new();
+    for (request, remote_port) in requests.iter().zip(remote_ports) {
+        let bind_addr = format!("{bind_host}:{}", request.local_port);
+        let bound = rt.block_on(async {
+            let listener = TcpListener::bind(&bind_addr).await?;
+            let local_port = listener.local_addr()?.port();
+            Ok::<_, std::io::Error>((listener, local_port))
+        });
+        let (listener, local_port) = match bound {
+            Ok(l) => l,
+            Err(e) => {
+                error!("pf#{id}: bind {bind_addr} failed: {e}");
+                return Err(mlua::Error::RuntimeError(format!(
+                    "bind {bind_addr} failed: {e}"
+                )));
+            }
+        };
+        listeners.push((listener, remote_port));
+        ports.push(ForwardedPort {
+            local_port,
+            remote_port,
+            name: match &request.remote_port {
+                RemotePort::Name(name) => Some(name.clone()),
+                RemotePort::Number(_) => None,
+            },
+        });
+    }
 
     let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
 
+    let stats = Arc::new(PFStats::default());
     let handle = rt.spawn(run_forward(
-        client.clone(),
-        pf_type,
-        name.clone(),
-        namespace.clone(),
-        listener,
-        remote_port,
+        target.clone(),
+        stats.clone(),
+        listeners,
         cancel_rx,
         id,
     ));
@@ -100,11 +215,13 @@ pub fn portforward_start(
         handle,
         cancel: Some(cancel_tx),
         pf_type,
+        context,
         name,
         namespace,
         host: bind_host,
-        local_port,
-        remote_port,
+        ports: ports.clone(),
+        target,
+        stats,
     };
 
     let pf_map = PF_MAP.get_or_init(|| Mutex::new(HashMap::new()));
@@ -113,7 +230,36 @@ pub fn portforward_start(
         .map_err(|_| mlua::Error::RuntimeError("poisoned PF_MAP lock".into()))?
         .insert(id, pf_data);
 
-    Ok(id)
+    Ok((id, ports))
+}
+
+fn forward_row(lua: &Lua, id: usize, pf: &PFData) -> LuaResult<LuaTable> {
+    let row = lua.create_table()?;
+    row.set("id", id)?;
+    row.set(
+        "type",
+        match pf.pf_type {
+            PFType::Pod => "pod",
+            PFType::Service => "service",
+        },
+    )?;
+    row.set("context", pf.context.clone())?;
+    row.set("name", pf.name.clone())?;
+    row.set("namespace", pf.namespace.clone())?;
+    row.set("host", pf.host.clone())?;
+    row.set("ports", lua.to_value(&pf.ports)?)?;
+    let (status, pod, last_error) = pf.target.snapshot();
+    row.set("status", status.as_str())?;
+    row.set("pod", pod)?;
+    row.set("error", last_error)?;
+    row.set("round_robin", matches!(pf.target.backend, Backend::Balanced(_)))?;
+    let stats = pf.stats.snapshot();
+    row.set("active_connections", stats.active_connections)?;
+    row.set("total_connections", stats.total_connections)?;
+    row.set("bytes_in", stats.bytes_in)?;
+    row.set("bytes_out", stats.bytes_out)?;
+    row.set("last_activity", stats.last_activity)?;
+    Ok(row)
 }
 
 pub fn portforward_list(lua: &Lua, _: ()) -> LuaResult<LuaTable> {
@@ -124,26 +270,27 @@ pub fn portforward_list(lua: &Lua, _: ()) -> LuaResult<LuaTable> {
         .lock()
         .map_err(|_| mlua::Error::RuntimeError("poisoned PF_MAP lock".into()))?;
     for (id, pf) in map.iter() {
-        let row = lua.create_table()?;
-        row.set("id", *id)?;
-        row.set(
-            "type",
-            match pf.pf_type {
-                PFType::Pod => "pod",
-                PFType::Service => "service",
-            },
-        )?;
-        row.set("name", pf.name.clone())?;
-        row.set("namespace", pf.namespace.clone())?;
-        row.set("host", pf.host.clone())?;
-        row.set("local_port", pf.local_port)?;
-        row.set("remote_port", pf.remote_port)?;
-        table.set(*id, row)?;
+        table.set(*id, forward_row(lua, *id, pf)?)?;
     }
 
     Ok(table)
 }
 
+/// A forward's row from `portforward_list` plus its recent connections, newest first.
+pub fn portforward_detail(lua: &Lua, id: usize) -> LuaResult<LuaTable> {
+    let pf_map = PF_MAP.get_or_init(|| Mutex::new(HashMap::new()));
+    let map = pf_map
+        .lock()
+        .map_err(|_| mlua::Error::RuntimeError("poisoned PF_MAP lock".into()))?;
+    let pf = map.get(&id).ok_or_else(|| {
+        mlua::Error::RuntimeError(format!("No port forward found for id {}", id))
+    })?;
+
+    let row = forward_row(lua, id, pf)?;
+    row.set("connections", lua.to_value(&pf.stats.connections())?)?;
+    Ok(row)
+}
+
 pub fn portforward_stop(_lua: &Lua, id: usize) -> LuaResult<()> {
     let pf_map = PF_MAP.get_or_init(|| Mutex::new(HashMap::new()));
 
@@ -175,33 +322,56 @@ pub fn portforward_stop(_lua: &Lua, id: usize) -> LuaResult<()> {
 }
 
 async fn run_forward(
-    client: Client,
-    pf_type: PFType,
-    name: String,
-    namespace: String,
-    listener: TcpListener,
-    remote_port: u16,
+    target: Arc<PFTarget>,
+    stats: Arc<PFStats>,
+    listeners: Vec<(TcpListener, u16)>,
     mut cancel_rx: oneshot::Receiver<()>,
     id: usize,
 ) {
     let mut conn_tasks: Vec<JoinHandle<()>> = Vec::new();
+    let mut accepts = futures::stream::select_all(listeners.into_iter().map(
+        |(listener, remote_port)| {
+            Box::pin(futures::stream::unfold(listener, move |listener| async move {
+                let accepted = listener.accept().await;
+                Some(((accepted, remote_port), listener))
+            }))
+        },
+    ));
+    let mut health = time::interval(HEALTH_INTERVAL);
+    health.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    // Checks run beside the loop so slow API calls never hold up accepts or a stop
+    let mut check: Option<JoinHandle<()>> = None;
 
     loop {
         tokio::select! {
             _ = &mut cancel_rx => {
                 break;
             }
-            accepted = listener.accept() => {
+            _ = health.tick() => {
+                if check.as_ref().is_none_or(|c| c.is_finished()) {
+                    let target = target.clone();
+                    check = Some(tokio::spawn(async move { target.check(id).await }));
+                }
+            }
+            Some((accepted, remote_port)) = accepts.next() => {
                 match accepted {
-                    Ok((sock, _peer)) => {
+                    Ok((sock, peer)) => {
                         let _ = sock.set_nodelay(true);
 
-                        let c = client.clone();
-                        let n = name.clone();
-                        let ns = namespace.clone();
+                        let target = target.clone();
+                        let stats = stats.clone();
                         let h = tokio::spawn(async move {
-                            if let Err(e) = handle_connection(c, pf_type, ns, n, remote_port, sock).await {
-                                warn!("pf#{id}: connection closed with error: {e}");
+                            let conn = stats.open(peer.to_string(), remote_port);
+                            let mut local = conn.count(sock);
+                            let result =
+                                handle_connection(&target, remote_port, &mut local, id).await;
+                            drop(local);
+                            match result {
+                                Ok(pod) => conn.finish(Some(pod), None),
+                                Err(e) => {
+                                    warn!("pf#{id}: connection closed with error: {e}");
+                                    conn.finish(target.snapshot().1, Some(e));
+                                }
                             }
                         });
                         conn_tasks.push(h);
@@ -212,12 +382,13 @@ async fn run_forward(
                     }
                     Err(e) => {
                         warn!("pf#{id}: accept error: {e} (retrying)");
-                        time::sleep(std::time::Duration::from_millis(200)).await;
+                        time::sleep(Duration::from_millis(200)).await;
                     }
                 }
             }
         }
     }
+    conn_tasks.extend(check);
     for h in &conn_tasks {
         h.abort();
     }
@@ -226,42 +397,407 @@ async fn run_forward(
     }
 }
 
+/// Connect a local socket to the forward's pod and return the pod used. A failed
+/// attempt drops the pod so the next one re-resolves the target; attempts back off.
 async fn handle_connection(
-    client: Client,
-    pf_type: PFType,
-    namespace: String,
-    name: String,
+    target: &PFTarget,
     remote_port: u16,
-    mut local: TcpStream,
-) -> PFResult<()> {
-    let pod = match pf_type {
-        PFType::Pod => name,
-        PFType::Service => resolve_pod_for_service(&client, &namespace, &name).await?,
-    };
-
-    let api: Api<Pod> = Api::namespaced(client, &namespace);
+    local: &mut (impl AsyncRead + AsyncWrite + Unpin),
+    id: usize,
+) -> PFResult<String> {
+    let api: Api<Pod> = Api::namespaced(target.client.clone(), &target.namespace);
     let mut last: Option<String> = None;
-    for backoff_ms in [0_u64, 75, 150] {
+    for backoff_ms in RETRY_BACKOFF_MS {
         if backoff_ms > 0 {
-            time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+            time::sleep(Duration::from_millis(backoff_ms)).await;
         }
+        let pod = match target.current_pod(id).await {
+            Ok(pod) => pod,
+            Err(e) => {
+                target.mark_reconnecting(&e);
+                last = Some(e);
+                continue;
+            }
+        };
         match api.portforward(&pod, &[remote_port]).await {
             Ok(mut pf) => {
                 if let Some(mut remote) = pf.take_stream(remote_port) {
-                    tokio::io::copy_bidirectional(&mut local, &mut remote)
+                    target.mark_active(&pod);
+                    tokio::io::copy_bidirectional(local, &mut remote)
                         .await
                         .map_err(err)?;
                     let _ = local.shutdown().await;
                     let _ = remote.shutdown().await;
-                    return Ok(());
+                    return Ok(pod);
                 } else {
                     last = Some(format!("no stream for remote port {remote_port}"));
                 }
             }
             Err(e) => last = Some(err(e)),
         }
+        if let Some(e) = &last {
+            warn!("pf#{id}: {pod}: {e}, re-resolving");
+            target.mark_reconnecting(e);
+        }
     }
-    Err(last.unwrap_or_else(|| "unknown port-forward error".into()))
+    let e = last.unwrap_or_else(|| "unknown port-forward error".into());
+    target.mark_failed(&e);
+    Err(e)
+}
+
+/// Where a forward's connections go.
+enum Backend {
+    /// A pod, replaced by a Ready pod of its owning workload if it has one
+    Pod(String),
+    Service(String),
+    /// Service whose connections rotate over the pods of its ready endpoints
+    Balanced(String),
+}
+
+/// Health of a forward, reported by `portforward_list`.
+struct TargetState {
+    /// Pod connections currently go to; `None` until resolved or after a failure
+    pod: Option<String>,
+    status: PFStatus,
+    last_error: Option<String>,
+}
+
+/// A forward's target. Services and pods owned by a workload are re-resolved to
+/// a Ready pod when the current one goes away; balanced services pick a pod per
+/// connection.
+pub struct PFTarget {
+    client: Client,
+    namespace: String,
+    backend: Backend,
+    state: Mutex<TargetState>,
+    /// Endpoint pods of a balanced service, refreshed by health checks and failures
+    endpoints: Mutex<Vec<String>>,
+    next: AtomicUsize,
+    /// Label selector of the workload owning a forwarded pod, looked up on first use
+    owner: tokio::sync::OnceCell<Option<String>>,
+}
+
+impl PFTarget {
+    fn new(
+        client: Client,
+        pf_type: PFType,
+        name: &str,
+        namespace: &str,
+        round_robin: bool,
+    ) -> Self {
+        let backend = match pf_type {
+            PFType::Service if round_robin => Backend::Balanced(name.to_string()),
+            PFType::Service => Backend::Service(name.to_string()),
+            PFType::Pod => Backend::Pod(name.to_string()),
+        };
+        let pod = match pf_type {
+            PFType::Pod => Some(name.to_string()),
+            PFType::Service => None,
+        };
+        Self {
+            client,
+            namespace: namespace.to_string(),
+            backend,
+            state: Mutex::new(TargetState {
+                pod,
+                status: PFStatus::Active,
+                last_error: None,
+            }),
+            endpoints: Mutex::new(Vec::new()),
+            next: AtomicUsize::new(0),
+            owner: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Selector of the workload owning `pod`. Failed lookups are retried on the next call.
+    async fn owner(&self, pod: &str, id: usize) -> Option<&str> {
+        let owner = self
+            .owner
+            .get_or_try_init(|| owner_selector(&self.client, &self.namespace, pod))
+            .await;
+        match owner {
+            Ok(selector) => selector.as_deref(),
+            Err(e) => {
+                warn!("pf#{id}: owner lookup of {pod} failed: {e}");
+                None
+            }
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut TargetState)) {
+        if let Ok(mut state) = self.state.lock() {
+            f(&mut state);
+        }
+    }
+
+    fn mark_active(&self, pod: &str) {
+        self.update(|s| {
+            s.pod = Some(pod.to_string());
+            s.status = PFStatus::Active;
+        });
+    }
+
+    /// Drop the current pod so the next attempt resolves the target again.
+    fn mark_reconnecting(&self, error: &str) {
+        if let Ok(mut endpoints) = self.endpoints.lock() {
+            endpoints.clear();
+        }
+        self.update(|s| {
+            s.pod = None;
+            s.status = PFStatus::Reconnecting;
+            s.last_error = Some(error.to_string());
+        });
+    }
+
+    fn mark_failed(&self, error: &str) {
+        self.update(|s| {
+            s.pod = None;
+            s.status = PFStatus::Failed;
+            s.last_error = Some(error.to_string());
+        });
+    }
+
+    /// Status, current pod and last error.
+    fn snapshot(&self) -> (PFStatus, Option<String>, Option<String>) {
+        match self.state.lock() {
+            Ok(s) => (s.status, s.pod.clone(), s.last_error.clone()),
+            Err(_) => (PFStatus::Failed, None, Some("poisoned state lock".into())),
+        }
+    }
+
+    async fn current_pod(&self, id: usize) -> PFResult<String> {
+        if let Backend::Balanced(svc) = &self.backend {
+            return self.next_endpoint(svc).await;
+        }
+        let cached = self.state.lock().ok().and_then(|s| s.pod.clone());
+        match cached {
+            Some(pod) => Ok(pod),
+            None => self.resolve(id).await,
+        }
+    }
+
+    async fn refresh_endpoints(&self, svc: &str) -> PFResult<Vec<String>> {
+        let pods = endpoint_pods(&self.client, &self.namespace, svc).await?;
+        if let Ok(mut endpoints) = self.endpoints.lock() {
+            endpoints.clone_from(&pods);
+        }
+        Ok(pods)
+    }
+
+    /// The next pod of a balanced service, in turn per connection.
+    async fn next_endpoint(&self, svc: &str) -> PFResult<String> {
+        let cached = self
+            .endpoints
+            .lock()
+            .map(|e| e.clone())
+            .unwrap_or_default();
+        let pods = if cached.is_empty() {
+            self.refresh_endpoints(svc).await?
+        } else {
+            cached
+        };
+        if pods.is_empty() {
+            return Err(format!("no ready endpoints with pods for service {svc}"));
+        }
+        let i = self.next.fetch_add(1, Ordering::Relaxed);
+        Ok(pods[i % pods.len()].clone())
+    }
+
+    /// Find a Ready pod for the backend.
+    async fn resolve(&self, id: usize) -> PFResult<String> {
+        let ns = self.namespace.as_str();
+        match &self.backend {
+            Backend::Pod(name) => {
+                if let Some(selector) = self.owner(name, id).await {
+                    return ready_pod(&self.client, ns, selector)
+                        .await?
+                        .ok_or_else(|| format!("no Ready pods found for {selector}"));
+                }
+                let pod = Api::<Pod>::namespaced(self.client.clone(), ns)
+                    .get(name)
+                    .await
+                    .map_err(err)?;
+                if is_pod_ready(&pod) {
+                    Ok(name.clone())
+                } else {
+                    Err(format!("pod {name} is not Ready"))
+                }
+            }
+            Backend::Service(svc) => resolve_pod_for_service(&self.client, ns, svc).await,
+            Backend::Balanced(svc) => self.next_endpoint(svc).await,
+        }
+    }
+
+    /// Verify the current pod is still Ready, moving to another one if it is not.
+    async fn check(&self, id: usize) {
+        if let Backend::Balanced(svc) = &self.backend {
+            match self.refresh_endpoints(svc).await {
+                Ok(pods) if !pods.is_empty() => self.update(|s| s.status = PFStatus::Active),
+                Ok(_) => {
+                    self.mark_failed(&format!("no ready endpoints with pods for service {svc}"))
+                }
+                Err(e) => warn!("pf#{id}: endpoint check of {svc} failed: {e}"),
+            }
+            return;
+        }
+        // Look the owner up while the forwarded pod is still around to be read
+        if let Backend::Pod(name) = &self.backend {
+            self.owner(name, id).await;
+        }
+
+        let (_, pod, _) = self.snapshot();
+        if let Some(pod) = &pod {
+            let api: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+            match api.get_opt(pod).await {
+                Ok(Some(p)) if is_pod_ready(&p) => return,
+                Ok(Some(_)) => self.mark_reconnecting(&format!("pod {pod} is not Ready")),
+                Ok(None) => self.mark_reconnecting(&format!("pod {pod} is gone")),
+                // Transient API errors say nothing about the pod
+                Err(e) => {
+                    warn!("pf#{id}: health check of {pod} failed: {e}");
+                    return;
+                }
+            }
+        }
+
+        match self.resolve(id).await {
+            Ok(next) => {
+                if pod.as_ref() != Some(&next) {
+                    info!("pf#{id}: now forwarding to {next}");
+                }
+                self.mark_active(&next);
+            }
+            Err(e) => self.mark_failed(&e),
+        }
+    }
+}
+
+/// Number of the container port called `name`.
+fn container_port(pod: &Pod, name: &str) -> Option<u16> {
+    pod.spec
+        .as_ref()?
+        .containers
+        .iter()
+        .flat_map(|c| c.ports.iter().flatten())
+        .find(|p| p.name.as_deref() == Some(name))
+        .and_then(|p| u16::try_from(p.container_port).ok())
+}
+
+/// Resolve a port name to a pod port: a service port name maps to its
+/// `targetPort`, which may itself name a container port; other names are
+/// container port names.
+fn named_port(svc: Option<&Service>, pod: &Pod, name: &str) -> Option<u16> {
+    let svc_port = svc
+        .and_then(|s| s.spec.as_ref())
+        .and_then(|s| s.ports.as_ref())
+        .and_then(|ports| ports.iter().find(|p| p.name.as_deref() == Some(name)));
+    match svc_port.map(|p| (p.port, p.target_port.as_ref())) {
+        Some((_, Some(IntOrString::Int(port)))) => u16::try_from(*port).ok(),
+        Some((_, Some(IntOrString::String(target)))) => container_port(pod, target),
+        Some((port, None)) => u16::try_from(port).ok(),
+        None => container_port(pod, name),
+    }
+}
+
+/// Remote port numbers of the requested mappings. Names are resolved through
+/// the spec of the current pod, as pods of one workload share their ports.
+async fn resolve_remote_ports(
+    target: &PFTarget,
+    requests: &[PortRequest],
+    id: usize,
+) -> PFResult<Vec<u16>> {
+    let numbers: Option<Vec<u16>> = requests
+        .iter()
+        .map(|r| match r.remote_port {
+            RemotePort::Number(port) => Some(port),
+            RemotePort::Name(_) => None,
+        })
+        .collect();
+    if let Some(numbers) = numbers {
+        return Ok(numbers);
+    }
+
+    let ns = target.namespace.as_str();
+    let pod_name = target.current_pod(id).await?;
+    let pod = Api::<Pod>::namespaced(target.client.clone(), ns)
+        .get(&pod_name)
+        .await
+        .map_err(err)?;
+    let svc = match &target.backend {
+        Backend::Service(svc) | Backend::Balanced(svc) => Some(
+            Api::<Service>::namespaced(target.client.clone(), ns)
+                .get(svc)
+                .await
+                .map_err(err)?,
+        ),
+        _ => None,
+    };
+
+    requests
+        .iter()
+        .map(|r| match &r.remote_port {
+            RemotePort::Number(port) => Ok(*port),
+            RemotePort::Name(name) => named_port(svc.as_ref(), &pod, name)
+                .ok_or_else(|| format!("no port named {name} on pod {pod_name}")),
+        })
+        .collect()
+}
+
+/// Selector of the workload controlling `pod`, following a ReplicaSet up to
+/// its Deployment so that pods of a new rollout match too. `None` for a pod
+/// without a controller.
+async fn owner_selector(client: &Client, ns: &str, pod: &str) -> PFResult<Option<String>> {
+    let pod = Api::<Pod>::namespaced(client.clone(), ns).get(pod).await.map_err(err)?;
+    let Some(owner) = pod
+        .metadata
+        .owner_references
+        .into_iter()
+        .flatten()
+        .find(|o| o.controller == Some(true))
+    else {
+        return Ok(None);
+    };
+
+    let (kind, name) = if owner.kind == "ReplicaSet" {
+        let rs = Api::<ReplicaSet>::namespaced(client.clone(), ns)
+            .get(&owner.name)
+            .await
+            .map_err(err)?;
+        rs.metadata
+            .owner_references
+            .into_iter()
+            .flatten()
+            .find(|o| o.controller == Some(true) && o.kind == "Deployment")
+            .map_or((owner.kind, owner.name), |d| (d.kind, d.name))
+    } else {
+        (owner.kind, owner.name)
+    };
+    // Other controllers have no selector to find a replacement pod with
+    let workloads = ["Deployment", "StatefulSet", "DaemonSet", "ReplicaSet", "Job"];
+    if !workloads.contains(&kind.as_str()) {
+        return Ok(None);
+    }
+
+    let target = LogSelector {
+        namespace: ns.to_string(),
+        kind: Some(kind),
+        name: Some(name),
+        ..LogSelector::default()
+    };
+    resolve_pod_selector(client, &target).await.map(Some)
+}
+
+async fn ready_pod(client: &Client, ns: &str, selector: &str) -> PFResult<Option<String>> {
+    let pods = Api::<Pod>::namespaced(client.clone(), ns)
+        .list(&ListParams::default().labels(selector))
+        .await
+        .map_err(err)?
+        .items;
+
+    Ok(pods
+        .into_iter()
+        .find(is_pod_ready)
+        .and_then(|p| p.metadata.name))
 }
 
 async fn resolve_pod_for_service(client: &Client, ns: &str, svc: &str) -> PFResult<String> {
@@ -279,18 +815,37 @@ async fn resolve_pod_for_service(client: &Client, ns: &str, svc: &str) -> PFResu
         .collect::<Vec<_>>()
         .join(",");
 
-    let pods = Api::<Pod>::namespaced(client.clone(), ns)
-        .list(&ListParams::default().labels(&selector_str))
-        .await
-        .map_err(err)?
-        .items;
+    ready_pod(client, ns, &selector_str)
+        .await?
+        .ok_or_else(|| format!("no Ready pods found for service {svc}"))
+}
 
-    let pod = pods
+/// Pods behind the ready endpoints of EndpointSlices, sorted and without duplicates.
+///
+/// Endpoints without a pod `targetRef` are skipped, as the API can only forward to
+/// pods; a nil `ready` condition counts as ready.
+fn ready_endpoint_pods(slices: &[EndpointSlice]) -> Vec<String> {
+    slices
+        .iter()
+        .flat_map(|slice| slice.endpoints.iter())
+        .filter(|ep| ep.conditions.as_ref().and_then(|c| c.ready) != Some(false))
+        .filter_map(|ep| ep.target_ref.as_ref())
+        .filter(|r| r.kind.as_deref() == Some("Pod"))
+        .filter_map(|r| r.name.clone())
+        .collect::<BTreeSet<_>>()
         .into_iter()
-        .find(is_pod_ready)
-        .and_then(|p| p.metadata.name);
+        .collect()
+}
 
-    pod.ok_or_else(|| format!("no Ready pods found for service {svc}"))
+/// Pods behind a service's ready endpoints. Reads EndpointSlices rather than the
+/// selector, so headless services and manually managed endpoints work too.
+async fn endpoint_pods(client: &Client, ns: &str, svc: &str) -> PFResult<Vec<String>> {
+    let slices = Api::<EndpointSlice>::namespaced(client.clone(), ns)
+        .list(&ListParams::default().labels(&format!("kubernetes.io/service-name={svc}")))
+        .await
+        .map_err(err)?
+        .items;
+    Ok(ready_endpoint_pods(&slices))
 }
 
 fn is_pod_ready(p: &Pod) -> bool {
@@ -300,3 +855,94 @@ fn is_pod_ready(p: &Pod) -> bool {
         .and_then(|conds| conds.iter().find(|c| c.type_ == "Ready"))
         .is_some_and(|c| c.status == "True")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{
+        Container, ContainerPort, ObjectReference, PodSpec, ServicePort, ServiceSpec,
+    };
+    use k8s_openapi::api::discovery::v1::{Endpoint, EndpointConditions};
+
+    #[test]
+    fn named_ports_resolve_through_service_and_pod() {
+        let port = |name: &str, number| ContainerPort {
+            name: Some(name.into()),
+            container_port: number,
+            ..Default::default()
+        };
+        let pod = Pod {
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "app".into(),
+                    ports: Some(vec![port("http", 8080), port("grpc", 9090)]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let svc_port = |name: &str, target| ServicePort {
+            name: Some(name.into()),
+            port: 80,
+            target_port: target,
+            ..Default::default()
+        };
+        let svc = Service {
+            spec: Some(ServiceSpec {
+                ports: Some(vec![
+                    svc_port("web", Some(IntOrString::String("http".into()))),
+                    svc_port("metrics", Some(IntOrString::Int(9100))),
+                    svc_port("plain", None),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(named_port(Some(&svc), &pod, "web"), Some(8080));
+        assert_eq!(named_port(Some(&svc), &pod, "metrics"), Some(9100));
+        assert_eq!(named_port(Some(&svc), &pod, "plain"), Some(80));
+        assert_eq!(named_port(Some(&svc), &pod, "grpc"), Some(9090));
+        assert_eq!(named_port(None, &pod, "web"), None);
+    }
+
+    #[test]
+    fn endpoint_pods_skip_unready_and_podless_endpoints() {
+        let endpoint = |kind: &str, name: &str, ready| Endpoint {
+            addresses: vec!["10.0.0.1".into()],
+            conditions: Some(EndpointConditions {
+                ready,
+                ..Default::default()
+            }),
+            target_ref: Some(ObjectReference {
+                kind: Some(kind.into()),
+                name: Some(name.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let slice = |endpoints| EndpointSlice {
+            address_type: "IPv4".into(),
+            endpoints,
+            ..Default::default()
+        };
+        let slices = [
+            slice(vec![
+                endpoint("Pod", "web-b", Some(true)),
+                endpoint("Pod", "web-c", Some(false)),
+                endpoint("Node", "node-1", Some(true)),
+            ]),
+            slice(vec![
+                endpoint("Pod", "web-a", None),
+                endpoint("Pod", "web-b", Some(true)),
+                Endpoint {
+                    addresses: vec!["192.168.1.10".into()],
+                    ..Default::default()
+                },
+            ]),
+        ];
+
+        assert_eq!(ready_endpoint_pods(&slices), vec!["web-a", "web-b"]);
+    }
+}
@@ -0,0 +1,235 @@
+use jiff::Timestamp;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// Finished connections kept per forward.
+const CONNECTION_LOG_SIZE: usize = 50;
+
+/// Traffic counters and recent connections of a forward.
+///
+/// "In" is traffic from local clients to the pod, "out" the responses back.
+#[derive(Default)]
+pub struct PFStats {
+    active: AtomicUsize,
+    total: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    /// Unix seconds of the last accepted connection or transferred byte; 0 = never
+    last_activity: AtomicI64,
+    log: Mutex<VecDeque<ConnectionRecord>>,
+}
+
+/// A finished connection, newest first in `PFStats::connections`.
+#[derive(Clone, Debug, Serialize)]
+pub struct ConnectionRecord {
+    pub peer: String,
+    pub remote_port: u16,
+    /// Pod the connection was forwarded to, if one was reached
+    pub pod: Option<String>,
+    pub started: String,
+    pub duration_ms: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub error: Option<String>,
+}
+
+/// Counter values at one point in time.
+#[derive(Debug, Serialize)]
+pub struct StatsSnapshot {
+    pub active_connections: usize,
+    pub total_connections: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub last_activity: Option<String>,
+}
+
+impl PFStats {
+    fn touch(&self) {
+        self.last_activity
+            .store(Timestamp::now().as_second(), Ordering::Relaxed);
+    }
+
+    /// Count a new connection; it stays active until the guard is dropped.
+    pub fn open(&self, peer: String, remote_port: u16) -> ConnectionGuard<'_> {
+        self.active.fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(1, Ordering::Relaxed);
+        self.touch();
+        ConnectionGuard {
+            stats: self,
+            peer,
+            remote_port,
+            started: Timestamp::now(),
+            clock: Instant::now(),
+            bytes_in: AtomicU64::new(0),
+            bytes_out: AtomicU64::new(0),
+        }
+    }
+
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let last = self.last_activity.load(Ordering::Relaxed);
+        StatsSnapshot {
+            active_connections: self.active.load(Ordering::Relaxed),
+            total_connections: self.total.load(Ordering::Relaxed),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            last_activity: (last > 0)
+                .then(|| Timestamp::from_second(last).ok())
+                .flatten()
+                .map(|ts| ts.to_string()),
+        }
+    }
+
+    /// Finished connections, newest first.
+    pub fn connections(&self) -> Vec<ConnectionRecord> {
+        self.log
+            .lock()
+            .map(|log| log.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// An open connection. Counts its traffic and keeps it active in `PFStats`.
+pub struct ConnectionGuard<'a> {
+    stats: &'a PFStats,
+    peer: String,
+    remote_port: u16,
+    started: Timestamp,
+    clock: Instant,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+}
+
+impl ConnectionGuard<'_> {
+    /// Wrap the local socket so bytes are counted while they flow.
+    pub fn count<S>(&self, inner: S) -> Counted<'_, S> {
+        Counted { inner, conn: self }
+    }
+
+    /// Add the connection to the log and end it.
+    pub fn finish(self, pod: Option<String>, error: Option<String>) {
+        let record = ConnectionRecord {
+            peer: self.peer.clone(),
+            remote_port: self.remote_port,
+            pod,
+            started: self.started.to_string(),
+            duration_ms: self.clock.elapsed().as_millis() as u64,
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            error,
+        };
+        if let Ok(mut log) = self.stats.log.lock() {
+            if log.len() == CONNECTION_LOG_SIZE {
+                log.pop_front();
+            }
+            log.push_back(record);
+        }
+    }
+}
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.stats.active.fetch_sub(1, Ordering::Relaxed);
+        self.stats.touch();
+    }
+}
+
+/// A local socket whose reads count as bytes in and writes as bytes out.
+pub struct Counted<'a, S> {
+    inner: S,
+    conn: &'a ConnectionGuard<'a>,
+}
+
+impl<S> Counted<'_, S> {
+    fn add(&self, n: usize, inbound: bool) {
+        if n == 0 {
+            return;
+        }
+        let (conn, total) = if inbound {
+            (&self.conn.bytes_in, &self.conn.stats.bytes_in)
+        } else {
+            (&self.conn.bytes_out, &self.conn.stats.bytes_out)
+        };
+        conn.fetch_add(n as u64, Ordering::Relaxed);
+        total.fetch_add(n as u64, Ordering::Relaxed);
+        self.conn.stats.touch();
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for Counted<'_, S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            self.add(buf.filled().len() - before, true);
+        }
+        poll
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for Counted<'_, S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let poll = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = poll {
+            self.add(n, false);
+        }
+        poll
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn counts_traffic_and_logs_connections() {
+        let stats = PFStats::default();
+        let (client, server) = tokio::io::duplex(64);
+        let mut client = client;
+
+        let conn = stats.open("127.0.0.1:5000".into(), 8080);
+        {
+            let mut local = conn.count(server);
+            client.write_all(b"ping").await.unwrap();
+            let mut buf = [0u8; 4];
+            local.read_exact(&mut buf).await.unwrap();
+            local.write_all(b"pong!").await.unwrap();
+        }
+        assert_eq!(stats.snapshot().active_connections, 1);
+        conn.finish(Some("web-0".into()), None);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.active_connections, 0);
+        assert_eq!(snapshot.total_connections, 1);
+        assert_eq!((snapshot.bytes_in, snapshot.bytes_out), (4, 5));
+        assert!(snapshot.last_activity.is_some());
+
+        let log = stats.connections();
+        assert_eq!(log.len(), 1);
+        assert_eq!((log[0].bytes_in, log[0].bytes_out), (4, 5));
+        assert_eq!(log[0].pod.as_deref(), Some("web-0"));
+    }
+}
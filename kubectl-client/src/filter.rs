@@ -1,62 +1,579 @@
-/// Filter `data` according to a comma-separated pattern list.
+use regex::Regex;
+
+/// A filter query compiled once and evaluated against every row.
+///
+/// * Terms are separated by commas (`,`) and **all** of them must match
+/// * Alternatives inside a term are separated by `|`; **any** may match.
+///   Without parentheses this only applies when every alternative is a field term
+///   or a regex, so `a|b` on its own stays a literal substring
+/// * Prefix a term with `!` for **negative** filtering
 ///
-/// * Multiple patterns are separated by commas (`,`)
-/// * Prefix a pattern with `!` for **negative** filtering
-/// * **All** patterns must match for the row to be kept
+/// A term is one of:
 ///
-/// A *pattern matches* an item when **any** of the supplied `fields`
-/// contains the pattern’s text.
+/// ```text
+/// foo                 any filterable field contains "foo"
+/// /^api-\d+/          any filterable field matches the regex
+/// status:Running      the field contains "Running"
+/// status=Running      the field equals "Running"
+/// name~^api-(a|b)     the field matches the regex (also `name~/.../`)
+/// restarts>5          numeric comparison on the field's sort key
+/// age<10m             duration comparison (s, m, h, d, w, y units)
+/// ```
 ///
 /// ```text
-/// "foo,!bar,baz"
-/// └── keep rows that
-///     ├─ contain "foo"
-///     ├─ contain "baz"
-///     └─ do **not** contain "bar"
+/// "namespace=prod, restarts>=3, !status:Running, (status:Error | status:CrashLoop)"
 /// ```
-#[tracing::instrument(skip(data, get_field_value))]
-pub fn filter_dynamic<'a, T, F>(
+///
+/// A term is only field-scoped when the table knows the field; otherwise the whole term
+/// is a substring, so values like `nginx:1.25` keep matching as typed. Values may be
+/// wrapped in double quotes to include `,` or `|`. Invalid regexes fall back to a literal
+/// substring match and incomplete comparisons (`restarts>`) are ignored, so the query
+/// stays usable while it is being typed.
+#[derive(Debug, Clone, Default)]
+pub struct FilterQuery {
+    /// AND over groups, OR within a group
+    groups: Vec<Group>,
+    /// Reference point for duration comparisons, in epoch seconds
+    now: i64,
+}
+
+#[derive(Debug, Clone)]
+struct Group {
+    terms: Vec<Term>,
+    /// The unparenthesized segment as one substring, used unless every alternative is a
+    /// field term or a regex
+    literal: Option<Term>,
+}
+
+#[derive(Debug, Clone)]
+struct Term {
+    negative: bool,
+    /// `field<op>value` form; `None` matcher is an incomplete comparison
+    scoped: Option<(String, Option<Matcher>)>,
+    /// Unscoped form: the whole term as a substring, or a `/regex/`
+    plain: Matcher,
+    regex: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Contains(String),
+    Equals(String),
+    Regex(Regex),
+    Compare(CmpOp, Operand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operand {
+    Number(f64),
+    /// Seconds
+    Duration(f64),
+}
+
+impl CmpOp {
+    fn eval(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            CmpOp::Lt => lhs < rhs,
+            CmpOp::Le => lhs <= rhs,
+            CmpOp::Gt => lhs > rhs,
+            CmpOp::Ge => lhs >= rhs,
+        }
+    }
+}
+
+impl FilterQuery {
+    pub fn new(query: &str) -> Self {
+        let groups = split_top_level(query, ',')
+            .into_iter()
+            .filter_map(|segment| {
+                let segment = segment.trim();
+                let inner = segment
+                    .strip_prefix('(')
+                    .and_then(|s| s.strip_suffix(')'));
+                let terms: Vec<Term> = split_top_level(inner.unwrap_or(segment), '|')
+                    .into_iter()
+                    .filter_map(Term::parse)
+                    .collect();
+                let literal = (inner.is_none() && terms.len() > 1)
+                    .then(|| Term::parse(segment))
+                    .flatten();
+                (!terms.is_empty()).then_some(Group { terms, literal })
+            })
+            .collect();
+
+        Self {
+            groups,
+            now: jiff::Timestamp::now().as_second(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// `text` is the processor's filter accessor, `sort_key` its sort accessor. Comparisons
+    /// read the sort key first and fall back to parsing the displayed text.
+    pub fn matches<T, F, S>(&self, item: &T, fields: &[&str], text: &F, sort_key: &S) -> bool
+    where
+        F: Fn(&T, &str) -> Option<String>,
+        S: Fn(&T, &str) -> Option<String>,
+    {
+        self.groups
+            .iter()
+            .all(|group| group.matches(item, fields, text, sort_key, self.now))
+    }
+}
+
+impl Group {
+    fn matches<T, F, S>(&self, item: &T, fields: &[&str], text: &F, sort_key: &S, now: i64) -> bool
+    where
+        F: Fn(&T, &str) -> Option<String>,
+        S: Fn(&T, &str) -> Option<String>,
+    {
+        if let Some(literal) = &self.literal {
+            let structured = self.terms.iter().all(|term| {
+                term.regex || term.scoped_field(item, fields, text, sort_key).is_some()
+            });
+            if !structured {
+                return literal
+                    .matches(item, fields, text, sort_key, now)
+                    .unwrap_or(true);
+            }
+        }
+
+        // Ignored terms (incomplete comparisons) don't take part
+        let mut results = self
+            .terms
+            .iter()
+            .filter_map(|term| term.matches(item, fields, text, sort_key, now))
+            .peekable();
+        results.peek().is_none() || results.any(|found| found)
+    }
+}
+
+impl Term {
+    fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let (negative, body) = match raw.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, raw),
+        };
+        if body.is_empty() {
+            return None;
+        }
+
+        if let Some(pattern) = strip_delimited(body, '/') {
+            return Some(Self {
+                negative,
+                scoped: None,
+                plain: regex_or_literal(pattern),
+                regex: true,
+            });
+        }
+
+        let scoped = split_field(body).map(|(field, op, value)| {
+            let matcher = match op {
+                ":" => Some(Matcher::Contains(unquote(value).to_string())),
+                "=" => Some(Matcher::Equals(unquote(value).to_string())),
+                "~" => Some(regex_or_literal(
+                    strip_delimited(value, '/').unwrap_or(unquote(value)),
+                )),
+                _ => parse_operand(value).map(|operand| {
+                    let op = match op {
+                        "<" => CmpOp::Lt,
+                        "<=" => CmpOp::Le,
+                        ">" => CmpOp::Gt,
+                        _ => CmpOp::Ge,
+                    };
+                    Matcher::Compare(op, operand)
+                }),
+            };
+            (field.to_lowercase(), matcher)
+        });
+
+        Some(Self {
+            negative,
+            scoped,
+            plain: Matcher::Contains(unquote(body).to_string()),
+            regex: false,
+        })
+    }
+
+    /// The term's field and matcher, when the field is one the table knows.
+    fn scoped_field<T, F, S>(
+        &self,
+        item: &T,
+        fields: &[&str],
+        text: &F,
+        sort_key: &S,
+    ) -> Option<(&str, Option<&Matcher>)>
+    where
+        F: Fn(&T, &str) -> Option<String>,
+        S: Fn(&T, &str) -> Option<String>,
+    {
+        let (field, matcher) = self.scoped.as_ref()?;
+        let known = fields.contains(&field.as_str())
+            || text(item, field).is_some()
+            || sort_key(item, field).is_some();
+        known.then_some((field.as_str(), matcher.as_ref()))
+    }
+
+    /// `None` when the term is an incomplete comparison and should be ignored.
+    fn matches<T, F, S>(
+        &self,
+        item: &T,
+        fields: &[&str],
+        text: &F,
+        sort_key: &S,
+        now: i64,
+    ) -> Option<bool>
+    where
+        F: Fn(&T, &str) -> Option<String>,
+        S: Fn(&T, &str) -> Option<String>,
+    {
+        let found = match self.scoped_field(item, fields, text, sort_key) {
+            Some((_, None)) => return None,
+            Some((field, Some(Matcher::Compare(op, operand)))) => {
+                compare(item, field, *op, *operand, text, sort_key, now)
+            }
+            Some((field, Some(matcher))) => {
+                text(item, field).is_some_and(|v| matcher.matches_text(&v))
+            }
+            None => fields.iter().any(|field| {
+                text(item, field)
+                    .as_deref()
+                    .is_some_and(|v| self.plain.matches_text(v))
+            }),
+        };
+
+        Some(found != self.negative)
+    }
+}
+
+impl Matcher {
+    fn matches_text(&self, value: &str) -> bool {
+        match self {
+            Matcher::Contains(pat) => value.contains(pat.as_str()),
+            Matcher::Equals(pat) => value == pat,
+            Matcher::Regex(re) => re.is_match(value),
+            Matcher::Compare(..) => false,
+        }
+    }
+}
+
+fn compare<T, F, S>(
+    item: &T,
+    field: &str,
+    op: CmpOp,
+    operand: Operand,
+    text: &F,
+    sort_key: &S,
+    now: i64,
+) -> bool
+where
+    F: Fn(&T, &str) -> Option<String>,
+    S: Fn(&T, &str) -> Option<String>,
+{
+    let key = sort_key(item, field).and_then(|k| k.trim().parse::<f64>().ok());
+
+    let lhs = match operand {
+        Operand::Number(_) => key.or_else(|| text(item, field).and_then(|v| leading_number(&v))),
+        // Time columns sort by their timestamp (seconds, or milliseconds for events),
+        // so turn that into elapsed time; otherwise parse a displayed age like "3d4h".
+        Operand::Duration(_) => key
+            .map(|ts| {
+                let secs = if ts > 1e11 { ts / 1000.0 } else { ts };
+                now as f64 - secs
+            })
+            .or_else(|| text(item, field).and_then(|v| parse_duration_secs(&v))),
+    };
+
+    let rhs = match operand {
+        Operand::Number(n) | Operand::Duration(n) => n,
+    };
+
+    lhs.is_some_and(|lhs| op.eval(lhs, rhs))
+}
+
+/// Split `field<op>value`. The field must look like an identifier so that plain
+/// substrings such as IPs or URLs are not mistaken for field terms.
+fn split_field(body: &str) -> Option<(&str, &str, &str)> {
+    let end = body
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        .unwrap_or(body.len());
+    let field = &body[..end];
+    if !field.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let rest = &body[end..];
+    let op = [">=", "<=", ">", "<", "=", "~", ":"]
+        .into_iter()
+        .find(|op| rest.starts_with(op))?;
+    Some((field, op, rest[op.len()..].trim()))
+}
+
+fn regex_or_literal(pattern: &str) -> Matcher {
+    match Regex::new(pattern) {
+        Ok(re) => Matcher::Regex(re),
+        Err(_) => Matcher::Contains(pattern.to_string()),
+    }
+}
+
+fn parse_operand(value: &str) -> Option<Operand> {
+    let value = unquote(value);
+    if let Ok(n) = value.parse::<f64>() {
+        return Some(Operand::Number(n));
+    }
+    parse_duration_secs(value).map(Operand::Duration)
+}
+
+/// Parse compound durations such as `90s`, `10m` or `1d12h` into seconds.
+fn parse_duration_secs(value: &str) -> Option<f64> {
+    let mut total = 0.0;
+    let mut num = String::new();
+    let mut seen_unit = false;
+
+    for c in value.trim().chars() {
+        if c.is_ascii_digit() || c == '.' {
+            num.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1.0,
+            'm' => 60.0,
+            'h' => 3600.0,
+            'd' => 86400.0,
+            'w' => 604800.0,
+            'y' => 31536000.0,
+            _ => return None,
+        };
+        total += num.parse::<f64>().ok()? * unit;
+        num.clear();
+        seen_unit = true;
+    }
+
+    (seen_unit && num.is_empty()).then_some(total)
+}
+
+/// Numeric prefix of a displayed value, e.g. `5` from `5 (2m ago)`.
+fn leading_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+fn unquote(value: &str) -> &str {
+    strip_delimited(value, '"').unwrap_or(value)
+}
+
+fn strip_delimited(value: &str, delim: char) -> Option<&str> {
+    value
+        .strip_prefix(delim)
+        .and_then(|v| v.strip_suffix(delim))
+        .filter(|_| value.len() >= 2)
+}
+
+/// Split on `sep`, ignoring separators inside parentheses, `"..."` or a `/.../` regex
+/// literal. A `/` only opens a regex at the start of a value and when a closing `/`
+/// follows, so `ns/name` and a lone leading `/` stay literal.
+fn split_top_level(input: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut prev: Option<char> = None;
+    let mut escaped = false;
+    let mut depth = 0usize;
+
+    for (i, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '"' => quote = Some('"'),
+            None if c == '/'
+                && matches!(prev, None | Some(',' | '|' | '(' | '!' | '~'))
+                && input[i + 1..].contains('/') =>
+            {
+                quote = Some('/')
+            }
+            None if c == '(' => depth += 1,
+            None if c == ')' => depth = depth.saturating_sub(1),
+            None if c == sep && depth == 0 => {
+                parts.push(&input[start..i]);
+                start = i + c.len_utf8();
+            }
+            None => {}
+        }
+        if !c.is_whitespace() {
+            prev = Some(c);
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+/// Filter `data` with a [`FilterQuery`] compiled from `patterns`.
+///
+/// Unscoped terms are matched against `fields`; field-scoped terms may name any field the
+/// accessors understand.
+#[tracing::instrument(skip(data, get_field_value, get_sort_key))]
+pub fn filter_dynamic<'a, T, F, S>(
     data: &'a [T],
     patterns: &str,
     fields: &[&str],
     get_field_value: F,
+    get_sort_key: S,
 ) -> Vec<&'a T>
 where
     F: Fn(&T, &str) -> Option<String>,
+    S: Fn(&T, &str) -> Option<String>,
 {
-    // ── 1. Split and normalize the pattern list ──────────────────────────────
-    let compiled: Vec<(bool /* negative? */, String /* text */)> = patterns
-        .split(',')
-        .filter_map(|raw| {
-            // ignore empty segments caused by stray commas
-            let raw = raw.trim();
-            if raw.is_empty() {
-                return None;
-            }
-            let negative = raw.starts_with('!');
-            let text = if negative { &raw[1..] } else { raw };
-            Some((negative, text.to_owned()))
-        })
-        .collect();
+    let query = FilterQuery::new(patterns);
+    if query.is_empty() {
+        return data.iter().collect();
+    }
 
-    // ── 2. Apply the AND-over-patterns / OR-over-fields logic ────────────────
     data.iter()
-        .filter(|item| {
-            compiled.iter().all(|(negative, pat)| {
-                let found = fields.iter().any(|field| {
-                    get_field_value(item, field)
-                        .as_deref()
-                        .is_some_and(|val| val.contains(pat))
-                });
-
-                if *negative {
-                    // Negative pattern: *must not* be found
-                    !found
-                } else {
-                    // Positive pattern: *must* be found
-                    found
-                }
-            })
-        })
+        .filter(|item| query.matches(*item, fields, &get_field_value, &get_sort_key))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Row {
+        name: &'static str,
+        status: &'static str,
+        image: &'static str,
+        restarts: usize,
+        created: i64,
+    }
+
+    fn text(row: &Row, field: &str) -> Option<String> {
+        match field {
+            "name" => Some(row.name.to_string()),
+            "status" => Some(row.status.to_string()),
+            "image" => Some(row.image.to_string()),
+            "restarts" => Some(format!("{} (1m ago)", row.restarts)),
+            _ => None,
+        }
+    }
+
+    fn sort_key(row: &Row, field: &str) -> Option<String> {
+        match field {
+            "restarts" => Some(crate::utils::pad_key(row.restarts)),
+            "age" => Some(row.created.to_string()),
+            _ => text(row, field),
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        let now = jiff::Timestamp::now().as_second();
+        vec![
+            Row {
+                name: "api-a",
+                status: "Running",
+                image: "nginx:1.25",
+                restarts: 0,
+                created: now - 3600,
+            },
+            Row {
+                name: "api-b",
+                status: "CrashLoopBackOff",
+                image: "nginx:1.27",
+                restarts: 7,
+                created: now - 120,
+            },
+            Row {
+                name: "worker",
+                status: "Error",
+                image: "busybox",
+                restarts: 4,
+                created: now - 86400 * 3,
+            },
+        ]
+    }
+
+    fn names(query: &str) -> Vec<&'static str> {
+        let data = rows();
+        filter_dynamic(&data, query, &["name", "status", "image"], text, sort_key)
+            .into_iter()
+            .map(|r| r.name)
+            .collect()
+    }
+
+    #[test]
+    fn legacy_substring_and_negation() {
+        assert_eq!(names("api"), vec!["api-a", "api-b"]);
+        assert_eq!(names("api,!Running"), vec!["api-b"]);
+        assert_eq!(names(""), vec!["api-a", "api-b", "worker"]);
+    }
+
+    #[test]
+    fn field_scoped_terms() {
+        assert_eq!(names("status:Crash"), vec!["api-b"]);
+        assert_eq!(names("status=Running"), vec!["api-a"]);
+        assert_eq!(names("name~^api-(a|b)$"), vec!["api-a", "api-b"]);
+        assert_eq!(names("/^w/"), vec!["worker"]);
+    }
+
+    #[test]
+    fn unknown_fields_match_as_substrings() {
+        assert_eq!(names("nginx:1.25"), vec!["api-a"]);
+        assert_eq!(names("image:nginx"), vec!["api-a", "api-b"]);
+        assert_eq!(names("api-a|worker"), Vec::<&str>::new());
+        assert_eq!(names("(api-a|worker)"), vec!["api-a", "worker"]);
+        assert_eq!(names("/api"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn numeric_and_duration_comparisons() {
+        assert_eq!(names("restarts>3"), vec!["api-b", "worker"]);
+        assert_eq!(names("restarts>3, !status:Error"), vec!["api-b"]);
+        assert_eq!(names("age<10m"), vec!["api-b"]);
+        assert_eq!(names("age>=1d"), vec!["worker"]);
+    }
+
+    #[test]
+    fn or_groups() {
+        assert_eq!(
+            names("(status:Error | status:Crash), restarts>5"),
+            vec!["api-b"]
+        );
+        assert_eq!(names("status=Running|name=worker"), vec!["api-a", "worker"]);
+    }
+
+    #[test]
+    fn incomplete_terms_do_not_hide_rows() {
+        assert_eq!(names("restarts>"), vec!["api-a", "api-b", "worker"]);
+        assert_eq!(names("name~(api"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration_secs("1d12h"), Some(129600.0));
+        assert_eq!(parse_duration_secs("10m"), Some(600.0));
+        assert_eq!(parse_duration_secs("10"), None);
+        assert_eq!(parse_duration_secs("5x"), None);
+    }
+}
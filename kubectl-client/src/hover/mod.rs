@@ -2,7 +2,7 @@ mod formatters;
 
 use crate::store;
 use crate::structs::GetSingleArgs;
-use crate::with_client;
+use crate::with_context_client;
 use formatters::format_resource;
 use k8s_openapi::serde_json;
 use kube::api::{Api, DynamicObject};
@@ -17,9 +17,11 @@ pub async fn get_hover_async(_lua: Lua, json: String) -> LuaResult<String> {
 
     let kind = args.gvk.k.clone();
 
-    with_client(move |client| async move {
+    let context = args.context.clone();
+    with_context_client(context.as_deref(), move |context, client| async move {
         // Try cache first
-        if let Some(obj) = store::get_single(&args.gvk.k, args.namespace.clone(), &args.name)?
+        if let Some(obj) =
+            store::get_single_in_context(&context, &args.gvk.k, args.namespace.clone(), &args.name)?
         {
             return Ok(format_resource(&kind, &obj));
         }
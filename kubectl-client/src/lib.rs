@@ -1,7 +1,7 @@
 // lib.rs
 use health::{get_health_status, shutdown_health_collector, spawn_health_collector};
 use k8s_openapi::serde_json;
-use kube::api::DynamicObject;
+use kube::api::{Api, ApiResource, DynamicObject};
 use kube::config::ExecInteractiveMode;
 use kube::{api::GroupVersionKind, config::KubeConfigOptions, Client, Config};
 use metrics::nodes::{shutdown_node_collector, spawn_node_collector, NodeStat, SharedNodeStats};
@@ -10,15 +10,22 @@ use mlua::prelude::*;
 use mlua::Lua;
 use std::collections::HashMap;
 use std::future::Future;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex, OnceLock, RwLock};
-use structs::{GetAllArgs, GetFallbackTableArgs, GetSingleArgs, GetTableArgs, StartReflectorArgs};
+use std::time::SystemTime;
+use structs::{
+    GetAllArgs, GetFallbackTableArgs, GetSingleArgs, GetTableArgs, GetTableDeltaArgs,
+    StartReflectorArgs,
+};
 use tokio::runtime::Runtime;
 
 use crate::cmd::get::get_resources_async;
-use crate::processors::processor::clear_resource_cache;
-use crate::processors::{processor_for, FilterParams};
+use crate::processors::processor::{
+    clear_resource_cache, drop_table_session, prune_resource_cache, table_session,
+};
+use crate::processors::{processor_for, FilterParams, WindowParams};
 use crate::statusline::get_statusline;
-use crate::store::shutdown_all_reflectors;
+use crate::store::{shutdown_all_reflectors, WatchSelector};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "telemetry")] {
@@ -55,7 +62,28 @@ static CLIENT_STREAM_INSTANCE: Mutex<Option<Client>> = Mutex::new(None);
 static ACTIVE_CONTEXT: RwLock<Option<String>> = RwLock::new(None);
 static POD_STATS: OnceLock<SharedPodStats> = OnceLock::new();
 static NODE_STATS: OnceLock<SharedNodeStats> = OnceLock::new();
-static BASE_CONFIG: Mutex<Option<Config>> = Mutex::new(None);
+static BASE_CONFIGS: OnceLock<Mutex<HashMap<String, Config>>> = OnceLock::new();
+static CONTEXT_CLIENTS: OnceLock<Mutex<HashMap<String, ContextClients>>> = OnceLock::new();
+/// Kubeconfig files with their modification times
+type KubeconfigStamp = Vec<(PathBuf, Option<SystemTime>)>;
+/// Stamp of the kubeconfig files when the configs were last loaded
+static KUBECONFIG_STAMP: Mutex<Option<KubeconfigStamp>> = Mutex::new(None);
+
+/// Kube clients built for a single kubeconfig context.
+/// `main` uses a short read timeout for requests, `stream` a long one for watches and logs.
+#[derive(Clone)]
+struct ContextClients {
+    main: Client,
+    stream: Client,
+}
+
+fn base_configs() -> &'static Mutex<HashMap<String, Config>> {
+    BASE_CONFIGS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn context_clients() -> &'static Mutex<HashMap<String, ContextClients>> {
+    CONTEXT_CLIENTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
 
 pub fn pod_stats() -> &'static SharedPodStats {
     POD_STATS.get_or_init(|| Arc::new(RwLock::new(HashMap::<PodKey, PodStat>::new())))
@@ -120,61 +148,241 @@ where
     block_on(f(client))
 }
 
-#[tracing::instrument]
-pub async fn init_client_async(_lua: Lua, _args: String) -> LuaResult<bool> {
-    use tokio::time::Duration;
-    let rt = RUNTIME.get_or_init(|| Runtime::new().expect("Failed to create Tokio runtime"));
+/// Name of the context the plugin is currently pointed at.
+pub fn active_context() -> String {
+    ACTIVE_CONTEXT
+        .read()
+        .ok()
+        .and_then(|ctx| ctx.clone())
+        .unwrap_or_default()
+}
+
+/// Drop derived caches of objects and contexts whose reflectors have shut down.
+/// Clients and configs of the active context are kept.
+pub fn release_stale_caches() {
+    let (live, mut contexts) = store::live_objects();
+    contexts.insert(active_context());
+    prune_resource_cache(&live, &contexts);
+    if let Ok(mut clients) = context_clients().lock() {
+        clients.retain(|context, _| contexts.contains(context));
+    }
+    if let Ok(mut configs) = base_configs().lock() {
+        configs.retain(|context, _| contexts.contains(context));
+    }
+}
 
-    let base_cfg = {
-        BASE_CONFIG
+/// The kubeconfig files in use (`KUBECONFIG` or `~/.kube/config`) with their mtimes.
+fn kubeconfig_stamp() -> KubeconfigStamp {
+    let paths: Vec<PathBuf> = match std::env::var_os("KUBECONFIG") {
+        Some(value) => std::env::split_paths(&value)
+            .filter(|p| !p.as_os_str().is_empty())
+            .collect(),
+        None => std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| vec![PathBuf::from(home).join(".kube").join("config")])
+            .unwrap_or_default(),
+    };
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Forget loaded configs and clients when a kubeconfig file changed since they were
+/// built, so edits such as refreshed credentials are picked up.
+fn reload_kubeconfig_if_changed() -> LuaResult<()> {
+    let stamp = kubeconfig_stamp();
+    let changed = {
+        let mut last = KUBECONFIG_STAMP
             .lock()
-            .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIG lock".into()))?
-            .clone()
-            .ok_or_else(|| {
-                LuaError::RuntimeError(
-                    "Base kube Config not prepared (call init_runtime first)".into(),
-                )
-            })?
+            .map_err(|_| LuaError::RuntimeError("poisoned KUBECONFIG_STAMP lock".into()))?;
+        let changed = last.as_ref().is_some_and(|old| *old != stamp);
+        *last = Some(stamp);
+        changed
+    };
+    if changed {
+        tracing::info!("kubeconfig changed, reloading contexts");
+        base_configs()
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIGS lock".into()))?
+            .clear();
+        context_clients()
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned CONTEXT_CLIENTS lock".into()))?
+            .clear();
+        store::restart_reflectors();
+    }
+    Ok(())
+}
+
+/// Resolve an optional context name, falling back to the kubeconfig's current-context.
+fn resolve_context_name(context_name: Option<String>) -> String {
+    context_name
+        .filter(|c| !c.is_empty())
+        .or_else(|| {
+            kube::config::Kubeconfig::read()
+                .ok()
+                .and_then(|k| k.current_context)
+        })
+        .unwrap_or_default()
+}
+
+/// Runs `f` with the client for `context` (or the active context when `None`).
+/// Clients for contexts other than the active one are built on first use and kept
+/// so their reflectors stay warm across context switches.
+pub fn with_context_client<F, Fut, R>(context: Option<&str>, f: F) -> LuaResult<R>
+where
+    F: FnOnce(String, Client) -> Fut,
+    Fut: Future<Output = LuaResult<R>>,
+{
+    let active = active_context();
+    let context = context
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .unwrap_or(active);
+
+    block_on(async move {
+        let client = context_client(&context).await?;
+        f(context, client).await
+    })
+}
+
+pub(crate) async fn context_client(context: &str) -> LuaResult<Client> {
+    if let Some(clients) = context_clients()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("poisoned CONTEXT_CLIENTS lock".into()))?
+        .get(context)
+    {
+        return Ok(clients.main.clone());
+    }
+
+    let clients = build_context_clients(context).await?;
+    let main = clients.main.clone();
+    context_clients()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("poisoned CONTEXT_CLIENTS lock".into()))?
+        .insert(context.to_string(), clients);
+    Ok(main)
+}
+
+async fn build_context_clients(context: &str) -> LuaResult<ContextClients> {
+    use tokio::time::Duration;
+
+    let cached = base_configs()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIGS lock".into()))?
+        .get(context)
+        .cloned();
+    let base_cfg = match cached {
+        Some(cfg) => cfg,
+        None => {
+            let cfg = load_base_config(Some(context.to_string())).await?;
+            base_configs()
+                .lock()
+                .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIGS lock".into()))?
+                .insert(context.to_string(), cfg.clone());
+            cfg
+        }
+    };
+
+    let mut cfg_fast = base_cfg.clone();
+    cfg_fast.read_timeout = Some(Duration::from_secs(20));
+    let mut cfg_long = base_cfg;
+    cfg_long.read_timeout = Some(Duration::from_secs(295));
+
+    let fast_task = tokio::spawn(async move { Client::try_from(cfg_fast) });
+    let long_task = tokio::spawn(async move { Client::try_from(cfg_long) });
+
+    let (client_main_res, client_long_res) = tokio::try_join!(fast_task, long_task)
+        .map_err(|e| LuaError::RuntimeError(format!("join error building clients: {e}")))?;
+
+    Ok(ContextClients {
+        main: client_main_res.map_err(LuaError::external)?,
+        stream: client_long_res.map_err(LuaError::external)?,
+    })
+}
+
+async fn load_base_config(context_name: Option<String>) -> LuaResult<Config> {
+    let opts = KubeConfigOptions {
+        context: context_name.filter(|c| !c.is_empty()),
+        cluster: None,
+        user: None,
     };
 
-    let cli_res: LuaResult<(Client, Client)> = rt.block_on(async {
-        let mut cfg_fast = base_cfg.clone();
-        cfg_fast.read_timeout = Some(Duration::from_secs(20));
-        let mut cfg_long = base_cfg.clone();
-        cfg_long.read_timeout = Some(Duration::from_secs(295));
+    let mut base_cfg = Config::from_kubeconfig(&opts)
+        .await
+        .map_err(LuaError::external)?;
 
-        let fast_task = tokio::spawn(async move { Client::try_from(cfg_fast) });
-        let long_task = tokio::spawn(async move { Client::try_from(cfg_long) });
+    if let Some(exec) = base_cfg.auth_info.exec.as_mut() {
+        exec.interactive_mode = Some(ExecInteractiveMode::Never);
+        if let Some(args) = exec.args.as_mut() {
+            if let Some(pos) = args.iter().position(|a| a == "devicecode") {
+                args[pos] = "azurecli".into();
+            }
+        }
+    }
+
+    Ok(base_cfg)
+}
 
-        let (client_main_res, client_long_res) = tokio::try_join!(fast_task, long_task)
-            .map_err(|e| LuaError::RuntimeError(format!("join error building clients: {e}")))?;
+#[tracing::instrument]
+pub async fn init_client_async(_lua: Lua, _args: String) -> LuaResult<bool> {
+    let rt = RUNTIME.get_or_init(|| Runtime::new().expect("Failed to create Tokio runtime"));
+    let context = active_context();
 
-        let client_main = client_main_res.map_err(LuaError::external)?;
-        let client_long = client_long_res.map_err(LuaError::external)?;
+    let cli_res: LuaResult<ContextClients> = rt.block_on(async {
+        let cached = context_clients()
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned CONTEXT_CLIENTS lock".into()))?
+            .get(&context)
+            .cloned();
+        let clients = match cached {
+            Some(clients) => clients,
+            None => {
+                if !base_configs()
+                    .lock()
+                    .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIGS lock".into()))?
+                    .contains_key(&context)
+                {
+                    return Err(LuaError::RuntimeError(
+                        "Base kube Config not prepared (call init_runtime first)".into(),
+                    ));
+                }
+                build_context_clients(&context).await?
+            }
+        };
 
-        client_main
+        clients
+            .main
             .apiserver_version()
             .await
             .map_err(LuaError::external)?;
-        Ok::<_, LuaError>((client_main, client_long))
+        Ok::<_, LuaError>(clients)
     });
 
-    let (client_main, client_long) = match cli_res {
-        Ok(pair) => pair,
+    let clients = match cli_res {
+        Ok(clients) => clients,
         Err(e) => {
             tracing::warn!(error = %e, "failed to initialise kube clients");
             return Ok(false);
         }
     };
 
+    context_clients()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("poisoned CONTEXT_CLIENTS lock".into()))?
+        .insert(context, clients.clone());
     *CLIENT_INSTANCE
         .lock()
         .map_err(|_| LuaError::RuntimeError("poisoned CLIENT_INSTANCE lock".into()))? =
-        Some(client_main.clone());
+        Some(clients.main);
     *CLIENT_STREAM_INSTANCE
         .lock()
         .map_err(|_| LuaError::RuntimeError("poisoned CLIENT_STREAM_INSTANCE lock".into()))? =
-        Some(client_long.clone());
+        Some(clients.stream);
 
     Ok(true)
 }
@@ -189,60 +397,47 @@ fn init_runtime(_lua: &Lua, context_name: Option<String>) -> LuaResult<(bool, St
     clear_node_stats();
 
     let rt = RUNTIME.get_or_init(|| Runtime::new().expect("create Tokio runtime"));
+    let context = resolve_context_name(context_name);
     {
         let mut ctx = ACTIVE_CONTEXT
             .write()
             .map_err(|_| LuaError::RuntimeError("poisoned ACTIVE_CONTEXT lock".into()))?;
-        *ctx = context_name.clone();
+        *ctx = Some(context.clone());
     }
+    reload_kubeconfig_if_changed()?;
+    release_stale_caches();
 
+    // Reflectors are keyed by context, so the previous context's watches stay warm.
+    // Only the active client slots are swapped here.
     let init_res: LuaResult<()> = rt.block_on(async {
-        let store_future = async {
-            shutdown_all_reflectors().await;
-            clear_resource_cache();
-            Ok::<(), LuaError>(())
-        };
-
-        let config_future = async {
-            let opts = KubeConfigOptions {
-                context: context_name.clone(),
-                cluster: None,
-                user: None,
-            };
-
-            let mut base_cfg = Config::from_kubeconfig(&opts)
-                .await
-                .map_err(LuaError::external)?;
-
-            if let Some(exec) = base_cfg.auth_info.exec.as_mut() {
-                exec.interactive_mode = Some(ExecInteractiveMode::Never);
-                if let Some(args) = exec.args.as_mut() {
-                    if let Some(pos) = args.iter().position(|a| a == "devicecode") {
-                        args[pos] = "azurecli".into();
-                    }
-                }
-            }
-
-            {
-                let mut slot = BASE_CONFIG
-                    .lock()
-                    .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIG lock".into()))?;
-                *slot = Some(base_cfg);
-            }
+        let has_config = base_configs()
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIGS lock".into()))?
+            .contains_key(&context);
+        if !has_config {
+            let base_cfg = load_base_config(Some(context.clone())).await?;
+            base_configs()
+                .lock()
+                .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIGS lock".into()))?
+                .insert(context.clone(), base_cfg);
+        }
 
-            {
-                *CLIENT_INSTANCE.lock().map_err(|_| {
-                    LuaError::RuntimeError("poisoned CLIENT_INSTANCE lock".into())
-                })? = None;
-                *CLIENT_STREAM_INSTANCE.lock().map_err(|_| {
-                    LuaError::RuntimeError("poisoned CLIENT_STREAM_INSTANCE lock".into())
-                })? = None;
-            }
+        let cached = context_clients()
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned CONTEXT_CLIENTS lock".into()))?
+            .get(&context)
+            .cloned();
 
-            Ok::<(), LuaError>(())
-        };
+        *CLIENT_INSTANCE
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned CLIENT_INSTANCE lock".into()))? =
+            cached.as_ref().map(|c| c.main.clone());
+        *CLIENT_STREAM_INSTANCE
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned CLIENT_STREAM_INSTANCE lock".into()))? =
+            cached.map(|c| c.stream);
 
-        tokio::try_join!(store_future, config_future).map(|_| ())
+        Ok::<(), LuaError>(())
     });
 
     if let Err(e) = init_res {
@@ -270,12 +465,22 @@ fn init_metrics(_lua: &Lua, _args: ()) -> LuaResult<bool> {
 fn get_all(_lua: &Lua, json: String) -> LuaResult<String> {
     let args: GetAllArgs = serde_json::from_str(&json)
         .map_err(|e| mlua::Error::external(format!("invalid JSON in get_all: {e}")))?;
-    with_client(move |client| async move {
-        let cached = store::get(&args.gvk.k, args.namespace.clone()).unwrap_or_default();
+    with_context_client(args.context.as_deref(), move |context, client| async move {
+        let selector = WatchSelector::new(args.label_selector, args.field_selector);
+        let cached =
+            store::get_in_context(&context, &args.gvk.k, args.namespace.clone(), &selector)
+                .unwrap_or_default();
         let resources: Vec<DynamicObject> = if cached.is_empty() {
-            get_resources_async(&client, args.gvk.k, args.gvk.g, args.gvk.v, args.namespace)
-                .await
-                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?
+            get_resources_async(
+                &client,
+                args.gvk.k,
+                args.gvk.g,
+                args.gvk.v,
+                args.namespace,
+                &selector,
+            )
+            .await
+            .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?
         } else {
             cached.iter().map(|obj| obj.as_ref().clone()).collect()
         };
@@ -291,12 +496,22 @@ fn get_all(_lua: &Lua, json: String) -> LuaResult<String> {
 async fn get_all_async(_lua: Lua, json: String) -> LuaResult<String> {
     let args: GetAllArgs = serde_json::from_str(&json)
         .map_err(|e| mlua::Error::external(format!("invalid JSON in get_all_async: {e}")))?;
-    with_client(move |client| async move {
-        let cached = store::get(&args.gvk.k, args.namespace.clone()).unwrap_or_default();
+    with_context_client(args.context.as_deref(), move |context, client| async move {
+        let selector = WatchSelector::new(args.label_selector, args.field_selector);
+        let cached =
+            store::get_in_context(&context, &args.gvk.k, args.namespace.clone(), &selector)
+                .unwrap_or_default();
         let resources: Vec<DynamicObject> = if cached.is_empty() {
-            get_resources_async(&client, args.gvk.k, args.gvk.g, args.gvk.v, args.namespace)
-                .await
-                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?
+            get_resources_async(
+                &client,
+                args.gvk.k,
+                args.gvk.g,
+                args.gvk.v,
+                args.namespace,
+                &selector,
+            )
+            .await
+            .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?
         } else {
             cached.iter().map(|obj| obj.as_ref().clone()).collect()
         };
@@ -313,10 +528,19 @@ async fn start_reflector_async(_lua: Lua, json: String) -> LuaResult<()> {
         mlua::Error::external(format!("invalid JSON in start_reflector_async: {e}"))
     })?;
 
-    with_client(move |client| async move {
+    with_context_client(args.context.as_deref(), move |context, client| async move {
         let gvk = GroupVersionKind::gvk(&args.gvk.g, &args.gvk.v, &args.gvk.k);
-        let _ = store::init_reflector_for_kind(client.clone(), gvk, args.namespace).await;
-        Ok(())
+        let selector = WatchSelector::new(args.label_selector, args.field_selector);
+        store::init_reflector_for_kind(
+            context,
+            client,
+            gvk,
+            args.namespace,
+            selector,
+            args.metadata_only.unwrap_or(false),
+        )
+        .await
+        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
     })
 }
 
@@ -333,7 +557,8 @@ pub async fn get_fallback_table_async(lua: Lua, json: String) -> LuaResult<Strin
         filter_label: args.filter_label,
         filter_key: args.filter_key,
     };
-    let processed = proc.process_fallback(&lua, args.gvk, args.namespace, &params)?;
+    let contexts = args.contexts.unwrap_or_default();
+    let processed = proc.process_fallback(&lua, args.gvk, args.namespace, &contexts, &params)?;
 
     serde_json::to_string(&processed).map_err(|e| mlua::Error::RuntimeError(e.to_string()))
 }
@@ -343,22 +568,42 @@ fn get_container_table(_lua: &Lua, json: String) -> LuaResult<String> {
     let args: GetSingleArgs =
         serde_json::from_str(&json).map_err(|e| mlua::Error::external(format!("bad json: {e}")))?;
 
-    let pod = store::get_single(&args.gvk.k, args.namespace.clone(), &args.name)
-        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
+    let context = args.context.clone().unwrap_or_else(active_context);
+    let pod = match store::get_single_in_context(
+        &context,
+        &args.gvk.k,
+        args.namespace.clone(),
+        &args.name,
+    )? {
+        Some(pod) => Some(pod),
+        // No pod reflector (not started yet or evicted) or the pod is gone: read it
+        // from the API, a deleted pod gives an empty table
+        None => with_context_client(Some(&context), |_, client| async move {
+            let ar = ApiResource::from_gvk(&GroupVersionKind::gvk(
+                &args.gvk.g,
+                &args.gvk.v,
+                &args.gvk.k,
+            ));
+            let api: Api<DynamicObject> = match &args.namespace {
+                Some(ns) => Api::namespaced_with(client, ns, &ar),
+                None => Api::all_with(client, &ar),
+            };
+            api.get_opt(&args.name).await.map_err(LuaError::external)
+        })?,
+    };
 
     let vec = match pod {
         Some(p) => vec![Arc::new(p)],
         None => Vec::new(),
     };
     let proc = processor_for("container");
-    proc.process(&vec, &FilterParams::default())
+    proc.process(&vec, &FilterParams::default(), None)
 }
 
 #[tracing::instrument]
 fn get_table(_lua: &Lua, json: String) -> LuaResult<String> {
     let args: GetTableArgs =
         serde_json::from_str(&json).map_err(|e| mlua::Error::external(format!("bad json: {e}")))?;
-    let cached = store::get(&args.gvk.k, args.namespace.clone()).unwrap_or_default();
     let proc = processor_for(&args.gvk.k.to_lowercase());
     let params = FilterParams {
         sort_by: args.sort_by,
@@ -367,7 +612,87 @@ fn get_table(_lua: &Lua, json: String) -> LuaResult<String> {
         filter_label: args.filter_label,
         filter_key: args.filter_key,
     };
-    proc.process(&cached, &params)
+
+    let selector = WatchSelector::new(args.label_selector, args.field_selector);
+    let window = WindowParams::new(args.offset, args.limit, args.locate);
+
+    // Aggregated mode: merge the kind from every requested context into one table
+    if let Some(contexts) = args.contexts.filter(|c| !c.is_empty()) {
+        let per_context: Vec<(String, Vec<Arc<DynamicObject>>)> = contexts
+            .into_iter()
+            .map(|context| {
+                let cached = store::get_in_context(
+                    &context,
+                    &args.gvk.k,
+                    args.namespace.clone(),
+                    &selector,
+                )
+                .unwrap_or_default();
+                (context, cached)
+            })
+            .collect();
+        return proc.process_contexts(&per_context, &params, window.as_ref());
+    }
+
+    let context = args.context.unwrap_or_else(active_context);
+    let cached = store::get_in_context(&context, &args.gvk.k, args.namespace.clone(), &selector)
+        .unwrap_or_default();
+    proc.process(&cached, &params, window.as_ref())
+}
+
+/// Incremental variant of `get_table`: only objects changed since the session's last call
+/// are rebuilt, and the result is a row-level diff instead of the full table.
+#[tracing::instrument]
+fn get_table_delta(_lua: &Lua, json: String) -> LuaResult<String> {
+    let args: GetTableDeltaArgs =
+        serde_json::from_str(&json).map_err(|e| mlua::Error::external(format!("bad json: {e}")))?;
+    let table = args.table;
+    let proc = processor_for(&table.gvk.k.to_lowercase());
+    let context = table.context.unwrap_or_else(active_context);
+    let selector = WatchSelector::new(table.label_selector, table.field_selector);
+
+    // Rows depend on these; changing any of them starts the session over
+    let scope = format!(
+        "{context}|{}/{}/{}|{:?}|{:?}|{:?}|{:?}",
+        table.gvk.g,
+        table.gvk.v,
+        table.gvk.k,
+        table.namespace,
+        selector,
+        table.filter_label,
+        table.filter_key,
+    );
+    // Held until the new rows are stored, so a refresh of the same session waits for it
+    let handle = table_session(&args.session)?;
+    let mut session = handle
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("table session lock poisoned".into()))?;
+    let cursor = if args.full.unwrap_or(false) {
+        None
+    } else {
+        session.cursor(&scope)
+    };
+
+    let params = FilterParams {
+        sort_by: table.sort_by,
+        sort_order: table.sort_order,
+        filter: table.filter,
+        filter_label: table.filter_label,
+        filter_key: table.filter_key,
+    };
+
+    let window = WindowParams::new(table.offset, table.limit, table.locate);
+
+    let delta =
+        store::get_changes_in_context(&context, &table.gvk.k, table.namespace, &selector, cursor)?;
+    proc.process_delta(
+        &mut session,
+        &context,
+        &scope,
+        &delta,
+        &params,
+        window.as_ref(),
+    )
 }
 
 #[tracing::instrument]
@@ -403,6 +728,14 @@ async fn shutdown_async(_lua: Lua, _args: ()) -> LuaResult<String> {
             .lock()
             .map_err(|_| LuaError::RuntimeError("poisoned CLIENT_STREAM_INSTANCE lock".into()))? =
             None;
+        context_clients()
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned CONTEXT_CLIENTS lock".into()))?
+            .clear();
+        base_configs()
+            .lock()
+            .map_err(|_| LuaError::RuntimeError("poisoned BASE_CONFIGS lock".into()))?
+            .clear();
     }
     {
         let mut ctx = ACTIVE_CONTEXT
@@ -448,6 +781,14 @@ fn kubectl_client(lua: &Lua) -> LuaResult<mlua::Table> {
         lua.create_function(get_container_table)?,
     )?;
     exports.set("get_table_async", lua.create_function(get_table)?)?;
+    exports.set("get_table_delta", lua.create_function(get_table_delta)?)?;
+    exports.set(
+        "drop_table_session",
+        lua.create_function(|_, session: String| {
+            drop_table_session(&session);
+            Ok(())
+        })?,
+    )?;
     exports.set(
         "get_fallback_table_async",
         lua.create_async_function(get_fallback_table_async)?,
@@ -503,6 +844,7 @@ fn kubectl_client(lua: &Lua) -> LuaResult<mlua::Table> {
     dao::install(lua, &exports)?;
     cmd::install(lua, &exports)?;
     event_queue::install(lua, &exports)?;
+    store::install(lua, &exports)?;
 
     Ok(exports)
 }
@@ -1,15 +1,18 @@
 pub mod nodes;
 pub mod pods;
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 /// Dirty flags for metrics - set when collectors update data, cleared after UI renders
 static POD_STATS_DIRTY: AtomicBool = AtomicBool::new(true);
 static NODE_STATS_DIRTY: AtomicBool = AtomicBool::new(true);
+/// Bumped on every pod collector update, so pod tables can tell metrics changed
+static POD_METRICS_GENERATION: AtomicU64 = AtomicU64::new(0);
 
 /// Marks pod stats as dirty (new data available). Called by the pod collector.
 pub fn mark_pod_stats_dirty() {
     POD_STATS_DIRTY.store(true, Ordering::Release);
+    POD_METRICS_GENERATION.fetch_add(1, Ordering::AcqRel);
 }
 
 /// Marks node stats as dirty (new data available). Called by the node collector.
@@ -17,6 +20,11 @@ pub fn mark_node_stats_dirty() {
     NODE_STATS_DIRTY.store(true, Ordering::Release);
 }
 
+/// Number of pod metrics updates so far. Unlike the dirty flags, reading it clears nothing.
+pub fn pod_metrics_generation() -> u64 {
+    POD_METRICS_GENERATION.load(Ordering::Acquire)
+}
+
 /// Checks if metrics data has changed since last render.
 /// Returns true and clears the flag if dirty, false otherwise.
 pub fn take_metrics_dirty() -> bool {
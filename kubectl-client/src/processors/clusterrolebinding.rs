@@ -6,7 +6,7 @@ use crate::events::{color_status, symbols};
 use crate::processors::processor::Processor;
 use crate::utils::{AccessorMode, FieldValue};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct ClusterRoleBindingProcessed {
     name: String,
     role: String,
@@ -3,9 +3,10 @@ use kube::api::DynamicObject;
 use mlua::prelude::*;
 
 use crate::processors::processor::Processor;
+use crate::store::is_metadata_only;
 use crate::utils::{pad_key, AccessorMode, FieldValue};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct ConfigmapProcessed {
     namespace: String,
     name: String,
@@ -22,15 +23,24 @@ impl Processor for ConfigmapProcessor {
     type Resource = ConfigMap;
 
     fn build_row(&self, map: &Self::Resource, obj: &DynamicObject) -> LuaResult<Self::Row> {
-        let binary_data = map.data.as_ref().map_or(0, |map| map.len());
+        // Metadata-only reflectors don't carry the data, so the count is unknown
+        let binary_data = if is_metadata_only(obj) {
+            FieldValue {
+                value: "<unknown>".into(),
+                ..Default::default()
+            }
+        } else {
+            let count = map.data.as_ref().map_or(0, |map| map.len());
+            FieldValue {
+                value: count.to_string(),
+                sort_by: Some(count),
+                ..Default::default()
+            }
+        };
         Ok(ConfigmapProcessed {
             namespace: map.metadata.namespace.clone().unwrap_or_default(),
             name: map.metadata.name.clone().unwrap_or_default(),
-            binary_data: FieldValue {
-                value: binary_data.to_string(),
-                sort_by: Some(binary_data),
-                ..Default::default()
-            },
+            binary_data,
             age: self.get_age(obj),
         })
     }
@@ -13,7 +13,7 @@ use mlua::{prelude::*, Error as LuaError};
 
 use super::processor::Processor;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct ContainerRow {
     name: String,
     image: String,
@@ -40,7 +40,7 @@ pub struct ContainerRow {
     age: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct PodContainersProcessed {
     namespace: String,
     pod: String,
@@ -5,7 +5,7 @@ use k8s_openapi::api::batch::v1::CronJob;
 use kube::api::DynamicObject;
 use mlua::prelude::*;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct CronJobProcessed {
     namespace: String,
     name: String,
@@ -103,6 +103,10 @@ impl Processor for CronJobProcessor {
         ]
     }
 
+    fn reads_clock(&self) -> bool {
+        true
+    }
+
     fn field_accessor(
         &self,
         mode: AccessorMode,
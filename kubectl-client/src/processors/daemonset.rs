@@ -4,7 +4,7 @@ use k8s_openapi::api::apps::v1::DaemonSet;
 use kube::api::DynamicObject;
 use mlua::prelude::*;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct DaemonsetProcessed {
     namespace: String,
     name: String,
@@ -7,7 +7,7 @@ use crate::events::color_status;
 use crate::processors::processor::Processor;
 use crate::utils::{pad_key, time_since_jiff, AccessorMode, FieldValue};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct EventProcessed {
     namespace: String,
     #[serde(rename = "last seen")]
@@ -88,6 +88,10 @@ impl Processor for EventProcessor {
         ]
     }
 
+    fn reads_clock(&self) -> bool {
+        true
+    }
+
     fn field_accessor(
         &self,
         mode: AccessorMode,
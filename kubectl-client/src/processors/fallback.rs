@@ -15,6 +15,7 @@ use tokio::try_join;
 use super::processor::{FilterParams, Processor};
 use crate::{
     cmd::utils::dynamic_api,
+    store::{self, WatchSelector},
     structs::Gvk,
     utils::{AccessorMode, FieldValue},
     with_client,
@@ -24,6 +25,8 @@ use crate::{
 struct PrinterCol {
     name: String,
     json_path: String,
+    /// OpenAPI type of the column: `integer`, `number`, `date`, `string`, `boolean`
+    type_: String,
 }
 
 #[derive(Debug, Clone)]
@@ -32,7 +35,7 @@ struct RuntimeFallbackProcessor {
     namespaced: bool,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 struct FallbackRow {
     namespace: Option<String>,
     name: String,
@@ -58,15 +61,7 @@ impl Processor for RuntimeFallbackProcessor {
                 .and_then(|v| v.as_str().map(str::to_string))
                 .unwrap_or_else(|| raw_val.map(|v| v.to_string()).unwrap_or_default());
 
-            extra.insert(
-                col.name.to_lowercase(),
-                FieldValue {
-                    value: str_val,
-                    symbol: None,
-                    sort_by: None,
-                    hint: None,
-                },
-            );
+            extra.insert(col.name.to_lowercase(), typed_value(&col.type_, str_val));
         }
 
         Ok(FallbackRow {
@@ -96,9 +91,10 @@ impl Processor for RuntimeFallbackProcessor {
                 AccessorMode::Sort => row.age.sort_by.map(|v| v.to_string()),
                 AccessorMode::Filter => Some(row.age.value.clone()),
             },
-            other => row.extra.get(other).and_then(|f| match mode {
-                AccessorMode::Sort => f.sort_by.map(|v| v.to_string()),
-                AccessorMode::Filter => Some(f.value.clone()),
+            other => row.extra.get(other).map(|f| match mode {
+                // Untyped columns sort by their text; numeric text is compared as a number
+                AccessorMode::Sort => f.sort_by.map_or_else(|| f.value.clone(), |v| v.to_string()),
+                AccessorMode::Filter => f.value.clone(),
             }),
         })
     }
@@ -140,9 +136,11 @@ impl Processor for FallbackProcessor {
         lua: &Lua,
         gvk: Gvk,
         ns: Option<String>,
+        contexts: &[String],
         params: &FilterParams,
     ) -> LuaResult<mlua::Value> {
         let params = params.clone();
+        let contexts = contexts.to_vec();
         with_client(move |client| async move {
             let gvk = GroupVersionKind {
                 group: gvk.g,
@@ -164,9 +162,18 @@ impl Processor for FallbackProcessor {
             let crd_api: Api<CustomResourceDefinition> = Api::all(client.clone());
             let crd_name = format!("{}.{}", ar.plural, gvk.group);
 
+            // Aggregated tables read the reflectors of each context instead of listing
             let lp = ListParams::default();
-            let (crd_opt, list) = try_join!(crd_api.get_opt(&crd_name), api.list(&lp),)
-                .map_err(LuaError::external)?;
+            let (crd_opt, items) = if contexts.is_empty() {
+                let (crd_opt, list) = try_join!(crd_api.get_opt(&crd_name), api.list(&lp),)
+                    .map_err(LuaError::external)?;
+                let items: Vec<Arc<DynamicObject>> =
+                    list.items.into_iter().map(Arc::new).collect();
+                (crd_opt, items)
+            } else {
+                let crd_opt = crd_api.get_opt(&crd_name).await.map_err(LuaError::external)?;
+                (crd_opt, Vec::new())
+            };
 
             let mut cols: Vec<PrinterCol> = if let Some(crd) = crd_opt {
                 crd.spec
@@ -179,6 +186,7 @@ impl Processor for FallbackProcessor {
                             .map(|c| PrinterCol {
                                 name: c.name.clone(),
                                 json_path: c.json_path.clone(),
+                                type_: c.type_.clone(),
                             })
                             .collect()
                     })
@@ -205,15 +213,30 @@ impl Processor for FallbackProcessor {
                 }
             });
 
-            let items: Vec<Arc<DynamicObject>> = list.items.into_iter().map(Arc::new).collect();
             let namespaced = matches!(caps.scope, Scope::Namespaced);
             let runtime = RuntimeFallbackProcessor {
                 cols: cols.clone(),
                 namespaced,
             };
 
-            let rows_vec = runtime.process(&items, &params)?;
-            let rows_lua = lua.to_value(&rows_vec)?;
+            let rows_lua = if contexts.is_empty() {
+                lua.to_value(&runtime.process(&items, &params)?)?
+            } else {
+                let per_context: Vec<(String, Vec<Arc<DynamicObject>>)> = contexts
+                    .into_iter()
+                    .map(|context| {
+                        let cached = store::get_in_context(
+                            &context,
+                            &gvk.kind,
+                            ns.clone(),
+                            &WatchSelector::default(),
+                        )
+                        .unwrap_or_default();
+                        (context, cached)
+                    })
+                    .collect();
+                lua.to_value(&runtime.process_contexts(&per_context, &params)?)?
+            };
 
             let mut headers: Vec<String> = canonical.iter().map(|s| s.to_string()).collect();
             headers.extend(cols.iter().map(|c| c.name.to_uppercase()));
@@ -227,6 +250,19 @@ impl Processor for FallbackProcessor {
     }
 }
 
+/// Date columns keep the value the CRD prints and sort by its timestamp.
+fn typed_value(type_: &str, value: String) -> FieldValue {
+    let sort_by = (type_ == "date")
+        .then(|| value.parse::<jiff::Timestamp>().ok())
+        .flatten()
+        .map(|ts| ts.as_second().max(0) as usize);
+    FieldValue {
+        value,
+        sort_by,
+        ..Default::default()
+    }
+}
+
 fn fix_crd_path(raw: &str) -> String {
     if raw.starts_with('.') {
         format!("${raw}")
@@ -6,7 +6,7 @@ use std::collections::BTreeMap;
 use crate::processors::processor::Processor;
 use crate::utils::{AccessorMode, FieldValue};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct HorizontalPodAutoscalerProcessed {
     namespace: String,
     name: String,
@@ -7,7 +7,7 @@ use std::collections::BTreeSet;
 use crate::processors::processor::Processor;
 use crate::utils::{AccessorMode, FieldValue};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct IngressProcessed {
     namespace: String,
     name: String,
@@ -6,7 +6,7 @@ use k8s_openapi::api::batch::v1::Job;
 use kube::api::DynamicObject;
 use mlua::prelude::*;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct JobProcessed {
     namespace: String,
     name: String,
@@ -77,6 +77,10 @@ impl Processor for JobProcessor {
         &["namespace", "name", "containers", "images", "duration"]
     }
 
+    fn reads_clock(&self) -> bool {
+        true
+    }
+
     fn field_accessor(
         &self,
         mode: AccessorMode,
@@ -6,7 +6,9 @@ use kube::api::DynamicObject;
 use mlua::{Lua, Result as LuaResult};
 use tracing::{span, Level};
 
+use crate::store::StoreDelta;
 use crate::structs::Gvk;
+use crate::utils::AccessorMode;
 
 use super::{
     clusterrole::ClusterRoleProcessor, clusterrolebinding::ClusterRoleBindingProcessor,
@@ -17,7 +19,7 @@ use super::{
     ingress::IngressProcessor, job::JobProcessor, namespace::NamespaceProcessor,
     node::NodeProcessor, persistentvolume::PersistentVolumeProcessor,
     persistentvolumeclaim::PersistentVolumeClaimProcessor, pod::PodProcessor,
-    processor::{FilterParams, Processor},
+    processor::{FilterParams, Processor, TableSession, WindowParams},
     replicaset::ReplicaSetProcessor, secret::SecretProcessor, service::ServiceProcessor,
     serviceaccount::ServiceAccountProcessor, statefulset::StatefulsetProcessor,
     storageclass::StorageClassProcessor,
@@ -93,17 +95,106 @@ pub fn processor_for(kind: &str) -> ProcessorKind {
     kind.parse().unwrap_or(ProcessorKind::Default)
 }
 
+/// Call `$run(&<processor>, args...)` with the processor for `$kind`.
+macro_rules! dispatch {
+    ($kind:expr, $run:ident($($arg:expr),* $(,)?)) => {{
+        use ProcessorKind::*;
+        match $kind {
+            ClusterRole => $run(&ClusterRoleProcessor, $($arg),*),
+            ClusterRoleBinding => $run(&ClusterRoleBindingProcessor, $($arg),*),
+            ConfigMap => $run(&ConfigmapProcessor, $($arg),*),
+            Container => $run(&ContainerProcessor, $($arg),*),
+            CronJob => $run(&CronJobProcessor, $($arg),*),
+            CustomResourceDefinition => $run(&ClusterResourceDefinitionProcessor, $($arg),*),
+            DaemonSet => $run(&DaemonsetProcessor, $($arg),*),
+            Default => $run(&DefaultProcessor, $($arg),*),
+            Deployment => $run(&DeploymentProcessor, $($arg),*),
+            Event => $run(&EventProcessor, $($arg),*),
+            Fallback => $run(&FallbackProcessor, $($arg),*),
+            HorizontalPodAutoscaler => $run(&HorizontalPodAutoscalerProcessor, $($arg),*),
+            Ingress => $run(&IngressProcessor, $($arg),*),
+            Job => $run(&JobProcessor, $($arg),*),
+            Namespace => $run(&NamespaceProcessor, $($arg),*),
+            Node => $run(&NodeProcessor, $($arg),*),
+            PersistentVolume => $run(&PersistentVolumeProcessor, $($arg),*),
+            PersistentVolumeClaim => $run(&PersistentVolumeClaimProcessor, $($arg),*),
+            Pod => $run(&PodProcessor, $($arg),*),
+            ReplicaSet => $run(&ReplicaSetProcessor, $($arg),*),
+            Secret => $run(&SecretProcessor, $($arg),*),
+            Service => $run(&ServiceProcessor, $($arg),*),
+            ServiceAccount => $run(&ServiceAccountProcessor, $($arg),*),
+            StatefulSet => $run(&StatefulsetProcessor, $($arg),*),
+            StorageClass => $run(&StorageClassProcessor, $($arg),*),
+        }
+    }};
+}
+
 #[tracing::instrument(skip(proc_impl, items))]
 fn run<P: Processor>(
     proc_impl: &P,
     items: &[Arc<DynamicObject>],
     params: &FilterParams,
+    window: Option<&WindowParams>,
 ) -> LuaResult<String> {
     let rows = proc_impl.process(items, params)?;
 
     let _json_span = span!(Level::INFO, "json_convert").entered();
 
-    serde_json::to_string(&rows).map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+    let json = match window {
+        Some(window) => {
+            let accessor = proc_impl.field_accessor(AccessorMode::Filter);
+            serde_json::to_string(&window.apply(&rows, |row| {
+                (accessor(row, "namespace"), accessor(row, "name"))
+            }))
+        }
+        None => serde_json::to_string(&rows),
+    };
+    json.map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+}
+
+#[tracing::instrument(skip(proc_impl, items))]
+fn run_contexts<P: Processor>(
+    proc_impl: &P,
+    items: &[(String, Vec<Arc<DynamicObject>>)],
+    params: &FilterParams,
+    window: Option<&WindowParams>,
+) -> LuaResult<String> {
+    let rows = proc_impl.process_contexts(items, params)?;
+
+    let _json_span = span!(Level::INFO, "json_convert").entered();
+
+    let json = match window {
+        Some(window) => {
+            let accessor = proc_impl.field_accessor(AccessorMode::Filter);
+            serde_json::to_string(&window.apply(&rows, |r| {
+                (accessor(&r.row, "namespace"), accessor(&r.row, "name"))
+            }))
+        }
+        None => serde_json::to_string(&rows),
+    };
+    json.map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+}
+
+#[tracing::instrument(skip(proc_impl, session, delta))]
+fn run_delta<P: Processor>(
+    proc_impl: &P,
+    session: &mut TableSession,
+    context: &str,
+    scope: &str,
+    delta: &StoreDelta,
+    params: &FilterParams,
+    window: Option<&WindowParams>,
+) -> LuaResult<String>
+where
+    P::Row: 'static,
+{
+    let diff = session.update(context, scope, delta.cursor, |state| {
+        proc_impl.process_delta(delta, state, params, window)
+    })?;
+
+    let _json_span = span!(Level::INFO, "json_convert").entered();
+
+    serde_json::to_string(&diff).map_err(|e| mlua::Error::RuntimeError(e.to_string()))
 }
 
 impl ProcessorKind {
@@ -113,11 +204,12 @@ impl ProcessorKind {
         lua: &Lua,
         gvk: Gvk,
         ns: Option<String>,
+        contexts: &[String],
         params: &FilterParams,
     ) -> LuaResult<mlua::Value> {
         match self {
             ProcessorKind::Fallback => {
-                FallbackProcessor.process_fallback(lua, gvk, ns, params)
+                FallbackProcessor.process_fallback(lua, gvk, ns, contexts, params)
             }
             _ => Err(mlua::Error::external(
                 "process_fallback is implemented only for the fallback processor",
@@ -129,34 +221,32 @@ impl ProcessorKind {
         &self,
         items: &[Arc<DynamicObject>],
         params: &FilterParams,
+        window: Option<&WindowParams>,
     ) -> LuaResult<String> {
-        use ProcessorKind::*;
-        match self {
-            ClusterRole => run(&ClusterRoleProcessor, items, params),
-            ClusterRoleBinding => run(&ClusterRoleBindingProcessor, items, params),
-            ConfigMap => run(&ConfigmapProcessor, items, params),
-            Container => run(&ContainerProcessor, items, params),
-            CronJob => run(&CronJobProcessor, items, params),
-            CustomResourceDefinition => run(&ClusterResourceDefinitionProcessor, items, params),
-            DaemonSet => run(&DaemonsetProcessor, items, params),
-            Default => run(&DefaultProcessor, items, params),
-            Deployment => run(&DeploymentProcessor, items, params),
-            Event => run(&EventProcessor, items, params),
-            Fallback => run(&FallbackProcessor, items, params),
-            HorizontalPodAutoscaler => run(&HorizontalPodAutoscalerProcessor, items, params),
-            Ingress => run(&IngressProcessor, items, params),
-            Job => run(&JobProcessor, items, params),
-            Namespace => run(&NamespaceProcessor, items, params),
-            Node => run(&NodeProcessor, items, params),
-            PersistentVolume => run(&PersistentVolumeProcessor, items, params),
-            PersistentVolumeClaim => run(&PersistentVolumeClaimProcessor, items, params),
-            Pod => run(&PodProcessor, items, params),
-            ReplicaSet => run(&ReplicaSetProcessor, items, params),
-            Secret => run(&SecretProcessor, items, params),
-            Service => run(&ServiceProcessor, items, params),
-            ServiceAccount => run(&ServiceAccountProcessor, items, params),
-            StatefulSet => run(&StatefulsetProcessor, items, params),
-            StorageClass => run(&StorageClassProcessor, items, params),
-        }
+        dispatch!(self, run(items, params, window))
+    }
+
+    pub fn process_contexts(
+        &self,
+        items: &[(String, Vec<Arc<DynamicObject>>)],
+        params: &FilterParams,
+        window: Option<&WindowParams>,
+    ) -> LuaResult<String> {
+        dispatch!(self, run_contexts(items, params, window))
+    }
+
+    pub fn process_delta(
+        &self,
+        session: &mut TableSession,
+        context: &str,
+        scope: &str,
+        delta: &StoreDelta,
+        params: &FilterParams,
+        window: Option<&WindowParams>,
+    ) -> LuaResult<String> {
+        dispatch!(
+            self,
+            run_delta(session, context, scope, delta, params, window)
+        )
     }
 }
@@ -6,7 +6,7 @@ use kube::api::DynamicObject;
 use mlua::prelude::*;
 use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct NodeProcessed {
     name: String,
     status: FieldValue,
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::{
     events::{color_status, symbols},
+    metrics::pod_metrics_generation,
     pod_stats,
     utils::{pad_key, time_since_jiff, AccessorMode, FieldValue},
 };
@@ -16,7 +17,7 @@ use mlua::prelude::*;
 
 use super::processor::Processor;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct PodProcessed {
     namespace: String,
     name: String,
@@ -143,6 +144,14 @@ impl Processor for PodProcessor {
         &["namespace", "name", "ready", "status", "ip", "node"]
     }
 
+    fn metrics_generation(&self) -> u64 {
+        pod_metrics_generation()
+    }
+
+    fn reads_clock(&self) -> bool {
+        true
+    }
+
     fn field_accessor(
         &self,
         mode: AccessorMode,
@@ -5,14 +5,15 @@ use mlua::prelude::*;
 use rayon::prelude::*;
 use serde::de::DeserializeOwned;
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
-use std::sync::{Arc, OnceLock, RwLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, TryLockError};
 
 use crate::{
     events::symbols,
     filter::filter_dynamic,
     sort::sort_dynamic,
+    store::{object_key, ChangeCursor, StoreDelta},
     structs::Gvk,
     utils::{time_since_jiff, AccessorMode, FieldValue},
 };
@@ -29,6 +30,100 @@ pub fn clear_resource_cache() {
     if let Some(c) = RESOURCE_CACHE.get() {
         let _ = c.write().map(|mut g| g.clear());
     }
+    if let Some(s) = TABLE_SESSIONS.get() {
+        let _ = s.lock().map(|mut g| g.clear());
+    }
+}
+
+/// Drop cached resources that no reflector holds anymore, and table sessions built
+/// for contexts outside `contexts`. Sessions in the middle of a refresh are kept.
+pub fn prune_resource_cache(live: &HashSet<usize>, contexts: &HashSet<String>) {
+    if let Some(c) = RESOURCE_CACHE.get() {
+        let _ = c.write().map(|mut g| g.retain(|ptr, _| live.contains(ptr)));
+    }
+    if let Some(s) = TABLE_SESSIONS.get() {
+        let _ = s.lock().map(|mut g| {
+            g.retain(|_, session| match session.try_lock() {
+                Ok(session) => contexts.contains(&session.context),
+                Err(TryLockError::WouldBlock) => true,
+                Err(TryLockError::Poisoned(_)) => false,
+            })
+        });
+    }
+}
+
+/// Rows kept between incremental `get_table_delta` calls, keyed by a caller-chosen session.
+#[derive(Default)]
+pub struct TableSession {
+    /// Context the rows were built for
+    context: String,
+    /// Context, kind, namespace, selectors and row filters the rows were built for
+    scope: String,
+    cursor: Option<ChangeCursor>,
+    state: Option<Box<dyn Any + Send + Sync>>,
+}
+
+type SharedTableSession = Arc<Mutex<TableSession>>;
+
+static TABLE_SESSIONS: OnceLock<Mutex<HashMap<String, SharedTableSession>>> = OnceLock::new();
+
+fn table_sessions() -> &'static Mutex<HashMap<String, SharedTableSession>> {
+    TABLE_SESSIONS.get_or_init(Default::default)
+}
+
+/// The session's shared state, created on first use.
+///
+/// Callers hold its lock for a whole refresh, from reading the cursor to storing the new
+/// rows, so overlapping refreshes of one session run one after the other.
+pub fn table_session(session: &str) -> LuaResult<SharedTableSession> {
+    let mut sessions = table_sessions()
+        .lock()
+        .map_err(|_| LuaError::RuntimeError("TABLE_SESSIONS lock poisoned".into()))?;
+    Ok(sessions.entry(session.to_string()).or_default().clone())
+}
+
+/// Forget a session's rows, e.g. once the buffer it was drawn into is wiped.
+pub fn drop_table_session(session: &str) {
+    if let Ok(mut sessions) = table_sessions().lock() {
+        sessions.remove(session);
+    }
+}
+
+impl TableSession {
+    /// Change cursor of the session, `None` if it is new or was built for a different scope.
+    pub fn cursor(&self, scope: &str) -> Option<ChangeCursor> {
+        self.cursor.filter(|_| self.scope == scope)
+    }
+
+    /// Run `f` on the session's row state, starting fresh if the scope or row type changed.
+    /// `cursor` is only stored when `f` succeeds, so a failed refresh is retried from the
+    /// same changes.
+    pub fn update<R, T>(
+        &mut self,
+        context: &str,
+        scope: &str,
+        cursor: Option<ChangeCursor>,
+        f: impl FnOnce(&mut TableState<R>) -> LuaResult<T>,
+    ) -> LuaResult<T>
+    where
+        R: Send + Sync + 'static,
+    {
+        let mut state: Box<TableState<R>> = self
+            .state
+            .take()
+            .filter(|_| self.scope == scope)
+            .and_then(|s| s.downcast().ok())
+            .unwrap_or_default();
+
+        let out = f(&mut state);
+        if out.is_ok() {
+            self.context = context.to_string();
+            self.scope = scope.to_string();
+            self.cursor = cursor;
+        }
+        self.state = Some(state);
+        out
+    }
 }
 
 fn deserialize_cached<T: DeserializeOwned + Send + Sync + 'static>(
@@ -70,7 +165,6 @@ pub fn dynamic_to_value(obj: &DynamicObject) -> LuaResult<Value> {
     Ok(Value::Object(map))
 }
 
-
 type FieldAccessorFn<'a, R> = Box<dyn Fn(&R, &str) -> Option<String> + 'a>;
 
 /// Parameters for filtering and sorting resource rows.
@@ -109,8 +203,137 @@ impl FilterParams {
     }
 }
 
+/// Visible slice of a sorted and filtered table, so only what the buffer shows is encoded.
+#[derive(Debug, Clone, Default)]
+pub struct WindowParams {
+    /// Rows to skip; defaults to the page that contains `locate`, else 0
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    /// `name` or `namespace/name` of a row whose position should be reported
+    pub locate: Option<String>,
+}
+
+/// Windowed table result returned instead of the plain row list.
+#[derive(Debug, serde::Serialize)]
+pub struct TableWindow<'a, R> {
+    /// Rows after filtering, before windowing
+    pub total: usize,
+    pub offset: usize,
+    pub rows: &'a [R],
+    /// 1-based position of the `locate` row in the full table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+impl WindowParams {
+    /// `None` when no windowing was asked for, so callers keep the plain row list.
+    pub fn new(offset: Option<usize>, limit: Option<usize>, locate: Option<String>) -> Option<Self> {
+        let locate = locate.filter(|l| !l.is_empty());
+        (offset.is_some() || limit.is_some() || locate.is_some()).then_some(Self {
+            offset,
+            limit,
+            locate,
+        })
+    }
+
+    /// `ident` returns a row's namespace and name.
+    pub fn apply<'a, R, F>(&self, rows: &'a [R], ident: F) -> TableWindow<'a, R>
+    where
+        F: Fn(&R) -> (Option<String>, Option<String>),
+    {
+        let index = self.locate.as_deref().and_then(|wanted| {
+            let (ns, name) = match wanted.split_once('/') {
+                Some((ns, name)) => (Some(ns), name),
+                None => (None, wanted),
+            };
+            rows.iter().position(|row| {
+                let (row_ns, row_name) = ident(row);
+                row_name.as_deref() == Some(name)
+                    && ns.is_none_or(|ns| row_ns.as_deref() == Some(ns))
+            })
+        });
+
+        let total = rows.len();
+        let offset = match (self.offset, self.limit, index) {
+            (Some(offset), ..) => offset,
+            (None, Some(limit), Some(i)) if limit > 0 => i - i % limit,
+            _ => 0,
+        }
+        .min(total);
+        let end = self.limit.map_or(total, |l| offset.saturating_add(l).min(total));
+
+        TableWindow {
+            total,
+            offset,
+            rows: &rows[offset..end],
+            index: index.map(|i| i + 1),
+        }
+    }
+}
+
+/// A processed row tagged with the kubeconfig context it came from.
+/// Used by aggregated tables that merge several contexts.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ContextRow<R> {
+    pub context: String,
+    #[serde(flatten)]
+    pub row: R,
+}
+
+/// A processed row tagged with its `namespace/name` key, used by incremental tables.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct KeyedRow<R> {
+    pub key: String,
+    #[serde(flatten)]
+    pub row: R,
+}
+
+/// Row state an incremental table carries between refreshes.
+#[derive(Debug)]
+pub struct TableState<R> {
+    primed: bool,
+    rows: HashMap<String, R>,
+    /// Keys of the rows sent to the caller, in display order
+    shown: Vec<String>,
+    /// Metrics generation the rows were built with
+    metrics_generation: u64,
+    /// Second the rows were built in, for processors whose rows read the clock
+    clock: i64,
+}
+
+impl<R> Default for TableState<R> {
+    fn default() -> Self {
+        Self {
+            primed: false,
+            rows: HashMap::new(),
+            shown: Vec::new(),
+            metrics_generation: 0,
+            clock: 0,
+        }
+    }
+}
+
+/// Row-level changes between two refreshes of an incremental table.
+///
+/// Rows are those inside the requested window. On a `full` refresh `added` holds every
+/// one of them. `order` is only set when the sequence of shown keys changed.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RowDiff<R> {
+    pub full: bool,
+    pub added: Vec<KeyedRow<R>>,
+    pub changed: Vec<KeyedRow<R>>,
+    pub removed: Vec<String>,
+    pub order: Option<Vec<String>>,
+    /// Rows after filtering, before windowing
+    pub total: usize,
+    pub offset: usize,
+    /// 1-based position of the `locate` row in the full table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
 pub trait Processor: Debug + Send + Sync {
-    type Row: Debug + Clone + Send + Sync + serde::Serialize;
+    type Row: Debug + Clone + PartialEq + Send + Sync + serde::Serialize;
     type Resource: DeserializeOwned + Send + Sync + 'static;
 
     /// Build a processed row from an already-deserialized typed resource.
@@ -164,12 +387,16 @@ pub trait Processor: Debug + Send + Sync {
         cur.as_str()
     }
 
-    #[tracing::instrument(skip(self, items), fields(item_count = items.len()))]
-    fn process(&self, items: &[Arc<DynamicObject>], params: &FilterParams) -> LuaResult<Vec<Self::Row>> {
+    /// Build rows for every item that passes the label and key filters, unsorted.
+    fn build_rows(
+        &self,
+        items: &[Arc<DynamicObject>],
+        params: &FilterParams,
+    ) -> LuaResult<Vec<Self::Row>> {
         let label_filters = params.parse_label_filters();
         let key_filters = params.parse_key_filters();
 
-        let mut rows: Vec<Self::Row> = items
+        items
             .par_iter()
             .filter(|obj| Self::labels_match(obj, &label_filters))
             .filter(|obj| Self::key_filters_match(obj, &key_filters))
@@ -178,7 +405,12 @@ pub trait Processor: Debug + Send + Sync {
                 self.build_row(&resource, obj).map_err(|e| e.to_string())
             })
             .collect::<Result<Vec<_>, _>>()
-            .map_err(LuaError::external)?;
+            .map_err(LuaError::external)
+    }
+
+    #[tracing::instrument(skip(self, items), fields(item_count = items.len()))]
+    fn process(&self, items: &[Arc<DynamicObject>], params: &FilterParams) -> LuaResult<Vec<Self::Row>> {
+        let mut rows = self.build_rows(items, params)?;
 
         sort_dynamic(
             &mut rows,
@@ -193,6 +425,220 @@ pub trait Processor: Debug + Send + Sync {
                 query,
                 self.filterable_fields(),
                 self.field_accessor(AccessorMode::Filter),
+                self.field_accessor(AccessorMode::Sort),
+            )
+            .into_iter()
+            .cloned()
+            .collect();
+        }
+
+        Ok(rows)
+    }
+
+    /// Number of metrics updates the rows read; a change rebuilds every row of an
+    /// incremental table, as metrics move without watch events.
+    fn metrics_generation(&self) -> u64 {
+        0
+    }
+
+    /// Whether rows show time-based text besides AGE, like a last-seen column; an
+    /// incremental table then rebuilds every row each second, as AGE alone is kept
+    /// current by the caller.
+    fn reads_clock(&self) -> bool {
+        false
+    }
+
+    /// Apply a store delta to the rows kept in `state` and report what changed.
+    ///
+    /// Only objects named in `delta.changed` are rebuilt, unless the metrics the rows show
+    /// moved on or the rows read a clock that ticked. The diff is `full` when the store has no usable change log for the caller
+    /// or the state is fresh, and covers only the rows inside `window`.
+    #[tracing::instrument(skip(self, delta, state), fields(item_count = delta.objects.len()))]
+    fn process_delta(
+        &self,
+        delta: &StoreDelta,
+        state: &mut TableState<Self::Row>,
+        params: &FilterParams,
+        window: Option<&WindowParams>,
+    ) -> LuaResult<RowDiff<Self::Row>> {
+        let label_filters = params.parse_label_filters();
+        let key_filters = params.parse_key_filters();
+        let full = !state.primed || delta.changed.is_none();
+        let generation = self.metrics_generation();
+        let clock = if self.reads_clock() {
+            Timestamp::now().as_second()
+        } else {
+            0
+        };
+        let rebuild_all = full || state.metrics_generation != generation || state.clock != clock;
+        let changed = delta.changed.as_ref().filter(|_| !rebuild_all);
+
+        let built: Vec<(String, Self::Row)> = delta
+            .objects
+            .par_iter()
+            .map(|obj| (object_key(obj), obj))
+            .filter(|(key, _)| changed.is_none_or(|c| c.contains(key)))
+            .filter(|(_, obj)| Self::labels_match(obj, &label_filters))
+            .filter(|(_, obj)| Self::key_filters_match(obj, &key_filters))
+            .map(|(key, obj)| {
+                let resource = deserialize_cached::<Self::Resource>(obj)?;
+                let row = self.build_row(&resource, obj).map_err(|e| e.to_string())?;
+                Ok((key, row))
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map_err(LuaError::external)?;
+
+        let mut updated: HashSet<String> = HashSet::new();
+        if !full {
+            // Deleted, or no longer matching the row filters
+            let rebuilt: HashSet<&String> = built.iter().map(|(key, _)| key).collect();
+            match changed {
+                Some(changed) => {
+                    for key in changed.iter().filter(|k| !rebuilt.contains(k)) {
+                        state.rows.remove(key);
+                    }
+                }
+                None => state.rows.retain(|key, _| rebuilt.contains(key)),
+            }
+            for (key, row) in built {
+                if state.rows.get(&key) != Some(&row) {
+                    updated.insert(key.clone());
+                }
+                state.rows.insert(key, row);
+            }
+        } else {
+            state.rows = built.into_iter().collect();
+        }
+        state.primed = true;
+        state.metrics_generation = generation;
+        state.clock = clock;
+
+        // Sort and filter every row again; this is cheap next to building rows
+        let mut entries: Vec<(&String, &Self::Row)> = state.rows.iter().collect();
+        let sort_accessor = self.field_accessor(AccessorMode::Sort);
+        sort_dynamic(
+            &mut entries,
+            params.sort_by.clone(),
+            params.sort_order.clone(),
+            |(_, row): &(&String, &Self::Row), field: &str| sort_accessor(row, field),
+        );
+        let visible: Vec<String> = match params.filter {
+            Some(ref query) => {
+                let filter_accessor = self.field_accessor(AccessorMode::Filter);
+                filter_dynamic(
+                    &entries,
+                    query,
+                    self.filterable_fields(),
+                    |(_, row): &(&String, &Self::Row), field: &str| filter_accessor(row, field),
+                    |(_, row): &(&String, &Self::Row), field: &str| sort_accessor(row, field),
+                )
+                .into_iter()
+                .map(|(key, _)| (*key).clone())
+                .collect()
+            }
+            None => entries.iter().map(|(key, _)| (*key).clone()).collect(),
+        };
+
+        let everything = WindowParams::default();
+        let window = window.unwrap_or(&everything).apply(&visible, |key: &String| {
+            match key.split_once('/') {
+                Some((ns, name)) => (Some(ns.to_string()), Some(name.to_string())),
+                None => (None, Some(key.clone())),
+            }
+        });
+        let shown = window.rows.to_vec();
+
+        let keyed = |key: &String| KeyedRow {
+            key: key.clone(),
+            row: state.rows[key].clone(),
+        };
+
+        let diff = if full {
+            RowDiff {
+                full,
+                added: shown.iter().map(keyed).collect(),
+                changed: Vec::new(),
+                removed: Vec::new(),
+                order: Some(shown.clone()),
+                total: window.total,
+                offset: window.offset,
+                index: window.index,
+            }
+        } else {
+            let before: HashSet<&String> = state.shown.iter().collect();
+            let after: HashSet<&String> = shown.iter().collect();
+            RowDiff {
+                full,
+                added: shown.iter().filter(|k| !before.contains(k)).map(keyed).collect(),
+                changed: shown
+                    .iter()
+                    .filter(|k| before.contains(k) && updated.contains(*k))
+                    .map(keyed)
+                    .collect(),
+                removed: state
+                    .shown
+                    .iter()
+                    .filter(|k| !after.contains(k))
+                    .cloned()
+                    .collect(),
+                order: (shown != state.shown).then(|| shown.clone()),
+                total: window.total,
+                offset: window.offset,
+                index: window.index,
+            }
+        };
+
+        state.shown = shown;
+        Ok(diff)
+    }
+
+    /// Merge rows for the same kind from several contexts into one table.
+    /// Each row carries its `context`, which sorting and filtering treat like any other column.
+    #[tracing::instrument(skip(self, items), fields(context_count = items.len()))]
+    fn process_contexts(
+        &self,
+        items: &[(String, Vec<Arc<DynamicObject>>)],
+        params: &FilterParams,
+    ) -> LuaResult<Vec<ContextRow<Self::Row>>> {
+        let mut rows = Vec::new();
+        for (context, objs) in items {
+            rows.extend(
+                self.build_rows(objs, params)?
+                    .into_iter()
+                    .map(|row| ContextRow {
+                        context: context.clone(),
+                        row,
+                    }),
+            );
+        }
+
+        let sort_accessor = self.field_accessor(AccessorMode::Sort);
+        sort_dynamic(
+            &mut rows,
+            params.sort_by.clone(),
+            params.sort_order.clone(),
+            |r: &ContextRow<Self::Row>, field: &str| match field {
+                "context" => Some(r.context.clone()),
+                _ => sort_accessor(&r.row, field),
+            },
+        );
+
+        if let Some(ref query) = params.filter {
+            let mut fields = vec!["context"];
+            fields.extend_from_slice(self.filterable_fields());
+            let filter_accessor = self.field_accessor(AccessorMode::Filter);
+            rows = filter_dynamic(
+                &rows,
+                query,
+                &fields,
+                |r: &ContextRow<Self::Row>, field: &str| match field {
+                    "context" => Some(r.context.clone()),
+                    _ => filter_accessor(&r.row, field),
+                },
+                |r: &ContextRow<Self::Row>, field: &str| match field {
+                    "context" => Some(r.context.clone()),
+                    _ => sort_accessor(&r.row, field),
+                },
             )
             .into_iter()
             .cloned()
@@ -245,8 +691,83 @@ pub trait Processor: Debug + Send + Sync {
         _lua: &Lua,
         _gvk: Gvk,
         _ns: Option<String>,
+        _contexts: &[String],
         _params: &FilterParams,
     ) -> LuaResult<mlua::Value> {
         Err(LuaError::external("Not implemented for this processor"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROWS: [&str; 5] = ["default/a", "default/b", "kube-system/c", "default/d", "e"];
+
+    fn ident(key: &&str) -> (Option<String>, Option<String>) {
+        match key.split_once('/') {
+            Some((ns, name)) => (Some(ns.to_string()), Some(name.to_string())),
+            None => (None, Some(key.to_string())),
+        }
+    }
+
+    fn window(offset: Option<usize>, limit: Option<usize>, locate: &str) -> WindowParams {
+        WindowParams::new(offset, limit, Some(locate.to_string())).unwrap_or_default()
+    }
+
+    #[test]
+    fn window_offset_past_the_end_is_empty() {
+        let w = window(Some(10), Some(2), "").apply(&ROWS, ident);
+        assert_eq!((w.total, w.offset, w.rows.len(), w.index), (5, 5, 0, None));
+    }
+
+    #[test]
+    fn window_limit_zero_still_locates() {
+        let w = window(None, Some(0), "default/d").apply(&ROWS, ident);
+        assert_eq!((w.offset, w.rows.len(), w.index), (0, 0, Some(4)));
+    }
+
+    #[test]
+    fn window_pages_to_the_located_row() {
+        let w = window(None, Some(2), "c").apply(&ROWS, ident);
+        assert_eq!((w.offset, w.rows, w.index), (2, &ROWS[2..4], Some(3)));
+
+        let w = window(None, Some(2), "e").apply(&ROWS, ident);
+        assert_eq!((w.offset, w.rows, w.index), (4, &ROWS[4..], Some(5)));
+    }
+
+    #[test]
+    fn window_locate_of_a_missing_row() {
+        let w = window(None, Some(2), "default/c").apply(&ROWS, ident);
+        assert_eq!((w.offset, w.rows, w.index), (0, &ROWS[..2], None));
+
+        let w = window(Some(1), None, "missing").apply(&ROWS, ident);
+        assert_eq!((w.offset, w.rows.len(), w.index), (1, 4, None));
+    }
+
+    #[test]
+    fn table_session_keeps_rows_of_a_failed_refresh() {
+        let mut session = TableSession::default();
+        let shown = |session: &mut TableSession, scope: &str| {
+            session
+                .update("ctx", scope, None, |state: &mut TableState<()>| {
+                    Ok(state.shown.clone())
+                })
+                .unwrap()
+        };
+
+        session
+            .update("ctx", "ctx|pods", None, |state: &mut TableState<()>| {
+                state.shown = vec!["default/a".into()];
+                Ok(())
+            })
+            .unwrap();
+        let failed = session.update("ctx", "ctx|pods", None, |_: &mut TableState<()>| {
+            Err::<(), _>(LuaError::external("boom"))
+        });
+        assert!(failed.is_err());
+
+        assert_eq!(shown(&mut session, "ctx|pods"), vec!["default/a"]);
+        assert!(shown(&mut session, "other|pods").is_empty());
+    }
+}
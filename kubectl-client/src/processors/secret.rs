@@ -3,15 +3,16 @@ use kube::api::DynamicObject;
 use mlua::prelude::*;
 
 use crate::processors::processor::Processor;
-use crate::utils::{AccessorMode, FieldValue};
+use crate::store::is_metadata_only;
+use crate::utils::{pad_key, AccessorMode, FieldValue};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct SecretProcessed {
     namespace: String,
     name: String,
     #[serde(rename = "type")]
     secret_type: String,
-    data: usize,
+    data: FieldValue,
     age: FieldValue,
 }
 
@@ -23,11 +24,31 @@ impl Processor for SecretProcessor {
     type Resource = Secret;
 
     fn build_row(&self, secret: &Self::Resource, obj: &DynamicObject) -> LuaResult<Self::Row> {
+        // Metadata-only reflectors don't carry the payload, so type and count are unknown
+        let unknown = is_metadata_only(obj);
+        let count = secret.data.as_ref().map_or(0, |data| data.len());
+        let data = if unknown {
+            FieldValue {
+                value: "<unknown>".into(),
+                ..Default::default()
+            }
+        } else {
+            FieldValue {
+                value: count.to_string(),
+                sort_by: Some(count),
+                ..Default::default()
+            }
+        };
+        let secret_type = if unknown {
+            "<unknown>".into()
+        } else {
+            secret.type_.clone().unwrap_or_default()
+        };
         Ok(SecretProcessed {
             namespace: secret.metadata.namespace.clone().unwrap_or_default(),
             name: secret.metadata.name.clone().unwrap_or_default(),
-            secret_type: secret.type_.clone().unwrap_or_default(),
-            data: secret.data.clone().unwrap_or_default().len(),
+            secret_type,
+            data,
             age: self.get_age(obj),
         })
     }
@@ -44,7 +65,10 @@ impl Processor for SecretProcessor {
             "namespace" => Some(resource.namespace.clone()),
             "name" => Some(resource.name.clone()),
             "type" => Some(resource.secret_type.clone().to_string()),
-            "data" => Some(resource.data.clone().to_string()),
+            "data" => match mode {
+                AccessorMode::Sort => resource.data.sort_by.map(pad_key),
+                AccessorMode::Filter => Some(resource.data.value.clone()),
+            },
             "age" => match mode {
                 AccessorMode::Sort => Some(resource.age.sort_by?.to_string()),
                 AccessorMode::Filter => Some(resource.age.value.clone()),
@@ -5,7 +5,7 @@ use mlua::prelude::*;
 use crate::processors::processor::Processor;
 use crate::utils::{AccessorMode, FieldValue};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct ServiceAccountProcessed {
     namespace: String,
     name: String,
@@ -4,7 +4,7 @@ use k8s_openapi::api::storage::v1::StorageClass;
 use kube::api::DynamicObject;
 use mlua::prelude::*;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct StorageClassProcessed {
     name: String,
     provisioner: String,
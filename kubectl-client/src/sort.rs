@@ -1,4 +1,128 @@
+use std::cmp::Ordering;
 use std::fmt::Debug;
+
+/// One key of a sort spec such as `"status asc, restarts desc, name"`.
+#[derive(Debug, Clone, PartialEq)]
+struct SortKey {
+    field: String,
+    desc: bool,
+}
+
+/// Typed view of an accessor value. Sort accessors return zero-padded or plain
+/// integers for numeric and time columns, so anything that parses as a number
+/// compares numerically; the rest compares as text.
+#[derive(Debug, Clone, PartialEq)]
+enum SortValue {
+    Missing,
+    Number(f64),
+    Text(String),
+}
+
+impl SortValue {
+    fn from_accessor(value: Option<String>) -> Self {
+        match value {
+            None => SortValue::Missing,
+            Some(s) => match s.trim().parse::<f64>() {
+                Ok(n) if n.is_finite() => SortValue::Number(n),
+                _ if s.is_empty() => SortValue::Missing,
+                _ => SortValue::Text(s),
+            },
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            SortValue::Missing => 0,
+            SortValue::Number(_) => 1,
+            SortValue::Text(_) => 2,
+        }
+    }
+}
+
+impl Eq for SortValue {}
+
+impl Ord for SortValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortValue::Number(a), SortValue::Number(b)) => a.total_cmp(b),
+            (SortValue::Text(a), SortValue::Text(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for SortValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct KeyPart {
+    value: SortValue,
+    desc: bool,
+}
+
+impl Ord for KeyPart {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ord = self.value.cmp(&other.value);
+        if self.desc {
+            ord.reverse()
+        } else {
+            ord
+        }
+    }
+}
+
+impl PartialOrd for KeyPart {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parse a comma-separated sort spec. Keys without an explicit `asc`/`desc`
+/// use `default_order`. A lone `namespace` key keeps its historical `name asc`
+/// tie-break.
+fn parse_spec(sort_by: Option<String>, default_order: &str) -> Vec<SortKey> {
+    let default_desc = default_order == "desc";
+    let mut keys: Vec<SortKey> = sort_by
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|part| {
+            let mut words = part.split_whitespace();
+            let field = words.next()?.to_lowercase();
+            let desc = match words.next().map(str::to_lowercase).as_deref() {
+                Some("desc") => true,
+                Some("asc") => false,
+                _ => default_desc,
+            };
+            Some(SortKey { field, desc })
+        })
+        .collect();
+
+    if keys.is_empty() {
+        keys.push(SortKey {
+            field: "namespace".to_owned(),
+            desc: default_desc,
+        });
+    }
+
+    if keys.len() == 1 && keys[0].field == "namespace" {
+        keys.push(SortKey {
+            field: "name".to_owned(),
+            desc: false,
+        });
+    }
+
+    keys
+}
+
+/// Sort `data` by one or more keys.
+///
+/// `sort_by` is either a single field, ordered by `sort_order`, or a spec like
+/// `"status asc, restarts desc, name asc"`. Values are read once per row through
+/// the sort accessor; numeric values compare numerically, so `"9"` sorts before
+/// `"10"` for CRD printer columns as well as built-in kinds.
 #[tracing::instrument(skip(data, get_field_value))]
 pub fn sort_dynamic<T, F>(
     data: &mut [T],
@@ -9,45 +133,94 @@ pub fn sort_dynamic<T, F>(
     T: Debug,
     F: Fn(&T, &str) -> Option<String>,
 {
-    use std::cmp::Ordering;
-
-    let field = sort_by
-        .map(|s| s.trim().to_lowercase())
-        .filter(|s| !s.is_empty())
-        .unwrap_or_else(|| "namespace".to_owned());
-
     let order = sort_order
         .map(|s| s.trim().to_lowercase())
         .filter(|s| !s.is_empty())
         .unwrap_or_else(|| "asc".to_owned());
 
-    data.sort_by(|a, b| {
-        if field == "namespace" {
-            let a_ns = get_field_value(a, "namespace").unwrap_or_default();
-            let b_ns = get_field_value(b, "namespace").unwrap_or_default();
+    let keys = parse_spec(sort_by, &order);
 
-            let ns_cmp = if order == "desc" {
-                b_ns.cmp(&a_ns)
-            } else {
-                a_ns.cmp(&b_ns)
-            };
+    data.sort_by_cached_key(|row| {
+        keys.iter()
+            .map(|key| KeyPart {
+                value: SortValue::from_accessor(get_field_value(row, &key.field)),
+                desc: key.desc,
+            })
+            .collect::<Vec<_>>()
+    });
+}
 
-            if ns_cmp == Ordering::Equal {
-                let a_name = get_field_value(a, "name").unwrap_or_default();
-                let b_name = get_field_value(b, "name").unwrap_or_default();
-                // Always ascending by name here
-                a_name.cmp(&b_name)
-            } else {
-                ns_cmp
-            }
-        } else {
-            let a_val = get_field_value(a, &field).unwrap_or_default();
-            let b_val = get_field_value(b, &field).unwrap_or_default();
-            if order == "desc" {
-                b_val.cmp(&a_val)
-            } else {
-                a_val.cmp(&b_val)
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Row {
+        name: &'static str,
+        status: &'static str,
+        restarts: &'static str,
+    }
+
+    fn get(row: &Row, field: &str) -> Option<String> {
+        match field {
+            "name" => Some(row.name.to_owned()),
+            "status" => Some(row.status.to_owned()),
+            "restarts" => Some(row.restarts.to_owned()),
+            _ => None,
         }
-    });
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                name: "c",
+                status: "Running",
+                restarts: "10",
+            },
+            Row {
+                name: "a",
+                status: "Error",
+                restarts: "9",
+            },
+            Row {
+                name: "b",
+                status: "Running",
+                restarts: "9",
+            },
+        ]
+    }
+
+    fn sorted(sort_by: &str, order: Option<&str>) -> Vec<&'static str> {
+        let mut data = rows();
+        sort_dynamic(
+            &mut data,
+            Some(sort_by.to_owned()),
+            order.map(str::to_owned),
+            get,
+        );
+        data.iter().map(|r| r.name).collect()
+    }
+
+    #[test]
+    fn numeric_values_sort_numerically() {
+        assert_eq!(sorted("restarts", None), vec!["a", "b", "c"]);
+        assert_eq!(sorted("restarts", Some("desc")), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn multi_key_spec() {
+        assert_eq!(
+            sorted("status desc, restarts desc, name", None),
+            vec!["c", "b", "a"]
+        );
+        assert_eq!(sorted("restarts, name desc", None), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn missing_values_sort_first() {
+        assert_eq!(
+            SortValue::from_accessor(None).cmp(&SortValue::from_accessor(Some("1".into()))),
+            Ordering::Less
+        );
+    }
 }
@@ -1,40 +1,318 @@
+use futures::stream::BoxStream;
 use futures::StreamExt;
 use k8s_openapi::serde_json::{self, json};
-use kube::api::TypeMeta;
+use kube::api::{PartialObjectMeta, TypeMeta};
 use kube::runtime::reflector::store::Writer;
 use kube::runtime::watcher::Event;
-use kube::runtime::{watcher, WatchStreamExt};
+use kube::runtime::{metadata_watcher, watcher, WatchStreamExt};
 use kube::{
-    api::{Api, ApiResource, DynamicObject, GroupVersionKind, ResourceExt},
+    api::{Api, ApiResource, DynamicObject, GroupVersionKind, ListParams, ResourceExt},
     Client,
 };
 
 use kube::runtime::reflector::Store;
-use std::collections::HashMap;
-use std::sync::{Arc, OnceLock, RwLock};
+use mlua::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
 use crate::event_queue::notify_named;
+use crate::structs::ReflectorStatusArgs;
+use crate::{active_context, context_client, release_stale_caches, RUNTIME};
+
+/// Idle reflectors are evicted after this many seconds (0 = never)
+static IDLE_TTL_SECS: AtomicU64 = AtomicU64::new(0);
+/// Upper bound on cached objects across all reflectors (0 = unlimited)
+static OBJECT_BUDGET: AtomicUsize = AtomicUsize::new(0);
+static SWEEPER: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+/// Reflectors read this recently belong to an open view and are never evicted
+const IN_USE_SECS: i64 = 2 * SWEEP_INTERVAL.as_secs() as i64;
+/// Keys kept per reflector for incremental table updates; older cursors get a full rebuild
+const CHANGE_LOG_CAPACITY: usize = 20_000;
+/// Source of change-log epochs, unique across reflectors so stale cursors never line up
+static NEXT_EPOCH: AtomicU64 = AtomicU64::new(1);
 
 pub struct ReflectorData {
     pub store: Store<DynamicObject>,
     pub handle: JoinHandle<()>,
     pub cancel: CancellationToken,
+    /// Store holds metadata only (PartialObjectMetadata watch), `data` is empty
+    pub metadata_only: bool,
+    /// Watched kind, kept so an evicted reflector can be started again
+    gvk: GroupVersionKind,
+    /// Unix seconds of the last read through `get`/`get_single_in_context`
+    last_access: AtomicI64,
+    /// Current sync state and last watch error, updated by the watcher task
+    status: watch::Receiver<ReflectorStatus>,
+    /// Keys of objects applied or deleted since the last relist
+    changes: Arc<Mutex<ChangeLog>>,
+    /// Bumped after every event the store applied, for [`Subscription::changed`]
+    revision: watch::Receiver<u64>,
+    /// Cloned into each [`Subscription`]; a reflector with subscribers is never evicted
+    lease: Arc<()>,
+}
+
+/// Live handle on a reflector for consumers inside the client, such as log followers.
+pub struct Subscription {
+    store: Store<DynamicObject>,
+    namespace: Option<String>,
+    revision: watch::Receiver<u64>,
+    status: watch::Receiver<ReflectorStatus>,
+    _lease: Arc<()>,
+}
+
+impl Subscription {
+    /// Objects currently cached, limited to the subscribed namespace.
+    pub fn objects(&self) -> Vec<Arc<DynamicObject>> {
+        self.store
+            .state()
+            .into_iter()
+            .filter(|obj| matches_namespace(obj, self.namespace.as_deref()))
+            .collect()
+    }
+
+    /// Wait until the store applied another event or the sync state changed.
+    pub async fn next(&mut self) -> SubscriptionEvent {
+        let status_changed = tokio::select! {
+            changed = self.revision.changed() => match changed {
+                Ok(()) => false,
+                Err(_) => return SubscriptionEvent::Stopped,
+            },
+            Ok(()) = self.status.changed() => true,
+        };
+        if status_changed {
+            SubscriptionEvent::Status(self.status.borrow_and_update().clone())
+        } else {
+            SubscriptionEvent::Changed
+        }
+    }
+}
+
+pub enum SubscriptionEvent {
+    Changed,
+    Status(ReflectorStatus),
+    /// The reflector was evicted or restarted; subscribe again to follow its replacement
+    Stopped,
+}
+
+/// Position in a reflector's change log, handed back by [`get_changes_in_context`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeCursor {
+    epoch: u64,
+    seq: u64,
+}
+
+/// Bounded log of object keys touched by Apply/Delete events.
+#[derive(Debug)]
+struct ChangeLog {
+    /// Replaced on every relist; cursors from another epoch need a full rebuild
+    epoch: u64,
+    /// Sequence number of `entries[0]`
+    base: u64,
+    entries: VecDeque<String>,
+}
+
+impl ChangeLog {
+    fn new() -> Self {
+        Self {
+            epoch: NEXT_EPOCH.fetch_add(1, Ordering::Relaxed),
+            base: 0,
+            entries: VecDeque::new(),
+        }
+    }
+
+    fn record(&mut self, key: String) {
+        self.entries.push_back(key);
+        if self.entries.len() > CHANGE_LOG_CAPACITY {
+            self.entries.pop_front();
+            self.base += 1;
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn cursor(&self) -> ChangeCursor {
+        ChangeCursor {
+            epoch: self.epoch,
+            seq: self.base + self.entries.len() as u64,
+        }
+    }
+
+    /// Keys changed after `cursor`, or `None` when the caller has to rebuild everything.
+    fn since(&self, cursor: Option<ChangeCursor>) -> Option<HashSet<String>> {
+        let cursor = cursor?;
+        if cursor.epoch != self.epoch || cursor.seq < self.base {
+            return None;
+        }
+        let skip = (cursor.seq - self.base) as usize;
+        Some(self.entries.iter().skip(skip).cloned().collect())
+    }
+}
+
+/// Store snapshot together with what changed since the caller's last cursor.
+#[derive(Debug, Default)]
+pub struct StoreDelta {
+    pub objects: Vec<Arc<DynamicObject>>,
+    /// `None` means everything must be rebuilt
+    pub changed: Option<HashSet<String>>,
+    pub cursor: Option<ChangeCursor>,
+}
+
+/// Key used for rows in incremental tables: `namespace/name`, or `name` for cluster scope.
+pub fn object_key(obj: &DynamicObject) -> String {
+    match obj.metadata.namespace.as_deref() {
+        Some(ns) => format!("{ns}/{}", obj.name_any()),
+        None => obj.name_any(),
+    }
+}
+
+/// Lifecycle of a reflector's watch as seen by the UI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    #[default]
+    Initializing,
+    Synced,
+    BackingOff,
+    Forbidden,
+}
+
+impl SyncState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SyncState::Initializing => "initializing",
+            SyncState::Synced => "synced",
+            SyncState::BackingOff => "backing_off",
+            SyncState::Forbidden => "forbidden",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReflectorStatus {
+    pub state: SyncState,
+    pub last_error: Option<String>,
+}
+
+impl ReflectorData {
+    fn touch(&self) {
+        self.last_access.store(now_secs(), Ordering::Relaxed);
+    }
+
+    fn last_access(&self) -> i64 {
+        self.last_access.load(Ordering::Relaxed)
+    }
+
+    fn idle_secs(&self) -> i64 {
+        now_secs() - self.last_access()
+    }
+
+    fn subscribed(&self) -> bool {
+        Arc::strong_count(&self.lease) > 1
+    }
+
+    fn shutdown(self) {
+        self.cancel.cancel();
+        self.handle.abort();
+    }
+}
+
+fn now_secs() -> i64 {
+    jiff::Timestamp::now().as_second()
+}
+
+/// Server-side label/field selectors passed to a reflector's watch.
+/// An empty selector watches every object of the kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WatchSelector {
+    pub labels: Option<String>,
+    pub fields: Option<String>,
+}
+
+impl WatchSelector {
+    pub fn new(labels: Option<String>, fields: Option<String>) -> Self {
+        let non_empty = |s: Option<String>| {
+            s.map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        Self {
+            labels: non_empty(labels),
+            fields: non_empty(fields),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_none() && self.fields.is_none()
+    }
+
+    /// List parameters equivalent to this selector, for one-shot lists.
+    pub fn list_params(&self) -> ListParams {
+        let mut lp = ListParams::default();
+        if let Some(labels) = &self.labels {
+            lp = lp.labels(labels);
+        }
+        if let Some(fields) = &self.fields {
+            lp = lp.fields(fields);
+        }
+        lp
+    }
+
+    fn apply(&self, mut config: watcher::Config) -> watcher::Config {
+        if let Some(labels) = &self.labels {
+            config = config.labels(labels);
+        }
+        if let Some(fields) = &self.fields {
+            config = config.fields(fields);
+        }
+        config
+    }
+}
+
+/// Identifies a reflector. `namespace: None` means all namespaces.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReflectorKey {
+    context: String,
+    kind: String,
+    namespace: Option<String>,
+    selector: WatchSelector,
 }
 
-/// Key: (kind, namespace) where None means all namespaces
-type ReflectorKey = (String, Option<String>);
 type StoreMap = Arc<RwLock<HashMap<ReflectorKey, ReflectorData>>>;
 
 static STORE_MAP: OnceLock<StoreMap> = OnceLock::new();
+/// Evicted reflectors with their kind and metadata-only flag, restarted on the next read
+static EVICTED: OnceLock<Mutex<HashMap<ReflectorKey, (GroupVersionKind, bool)>>> =
+    OnceLock::new();
 
 fn store_map() -> &'static StoreMap {
     STORE_MAP.get_or_init(|| Arc::new(RwLock::new(HashMap::new())))
 }
 
-fn key(kind: &str, namespace: Option<&str>) -> ReflectorKey {
-    (kind.to_string(), namespace.map(String::from))
+fn evicted_map() -> &'static Mutex<HashMap<ReflectorKey, (GroupVersionKind, bool)>> {
+    EVICTED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn key(
+    context: &str,
+    kind: &str,
+    namespace: Option<&str>,
+    selector: &WatchSelector,
+) -> ReflectorKey {
+    ReflectorKey {
+        context: context.to_string(),
+        kind: kind.to_string(),
+        namespace: namespace.map(String::from),
+        selector: selector.clone(),
+    }
 }
 
 #[tracing::instrument]
@@ -43,104 +321,207 @@ pub async fn shutdown_all_reflectors() {
         tracing::warn!("STORE_MAP lock poisoned during shutdown");
         return;
     };
-    for ((kind, ns), data) in map.drain() {
-        tracing::debug!(kind, ?ns, "Shutting down reflector");
-        data.cancel.cancel();
-        data.handle.abort();
+    for (key, data) in map.drain() {
+        tracing::debug!(key.context, key.kind, ns = ?key.namespace, "Shutting down reflector");
+        data.shutdown();
+    }
+    if let Ok(mut evicted) = evicted_map().lock() {
+        evicted.clear();
     }
 }
 
-fn shutdown_namespaced_reflectors(map: &mut HashMap<ReflectorKey, ReflectorData>, kind: &str) {
+fn shutdown_namespaced_reflectors(
+    map: &mut HashMap<ReflectorKey, ReflectorData>,
+    context: &str,
+    kind: &str,
+    selector: &WatchSelector,
+) {
     let to_remove: Vec<_> = map
         .keys()
-        .filter(|(k, ns)| k == kind && ns.is_some())
+        .filter(|k| {
+            k.context == context
+                && k.kind == kind
+                && k.namespace.is_some()
+                && &k.selector == selector
+        })
         .cloned()
         .collect();
 
     for key in to_remove {
         if let Some(data) = map.remove(&key) {
-            tracing::debug!(kind, ns = ?key.1, "Shutting down namespaced reflector");
-            data.cancel.cancel();
-            data.handle.abort();
+            tracing::debug!(context, kind, ns = ?key.namespace, "Shutting down namespaced reflector");
+            data.shutdown();
         }
     }
 }
 
 #[tracing::instrument(skip(client))]
 pub async fn init_reflector_for_kind(
+    context: String,
     client: Client,
     gvk: GroupVersionKind,
     namespace: Option<String>,
+    selector: WatchSelector,
+    metadata_only: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut map = store_map().write().map_err(|_| "STORE_MAP lock poisoned")?;
-    let kind = &gvk.kind;
-    let requested_key = key(kind, namespace.as_deref());
+    // The lock is released before waiting for the initial sync
+    let (reader, mut status) = {
+        let mut map = store_map().write().map_err(|_| "STORE_MAP lock poisoned")?;
+        let kind = &gvk.kind;
+        let requested_key = key(&context, kind, namespace.as_deref(), &selector);
 
-    // Check if reflector already exists or can be reused
-    let all_key = key(kind, None);
-    if map.contains_key(&all_key) || map.contains_key(&requested_key) {
-        return Ok(());
-    }
+        // Check if reflector already exists or can be reused.
+        // A full reflector satisfies a metadata-only request, but not the other way around.
+        let all_key = key(&context, kind, None, &selector);
+        let reusable = |k: &ReflectorKey| {
+            map.get(k)
+                .is_some_and(|data| metadata_only || !data.metadata_only)
+        };
+        for k in [&all_key, &requested_key] {
+            if reusable(k) {
+                let status = map.get(k).map(|data| data.status.borrow().clone());
+                return match status {
+                    Some(ReflectorStatus {
+                        state: SyncState::Forbidden,
+                        last_error,
+                    }) => Err(last_error.unwrap_or_else(|| "forbidden".into()).into()),
+                    _ => Ok(()),
+                };
+            }
+        }
 
-    // Starting "All" reflector - shutdown redundant namespaced ones
-    if namespace.is_none() {
-        shutdown_namespaced_reflectors(&mut map, kind);
-    }
+        // Upgrading from metadata-only to full objects - replace the metadata reflector
+        if let Some(data) = map.remove(&requested_key) {
+            tracing::debug!(
+                context,
+                kind,
+                ?namespace,
+                "Replacing metadata-only reflector"
+            );
+            data.shutdown();
+        }
 
-    let (reflector, reader) = create_reflector(client, &gvk, namespace).await?;
-    map.insert(requested_key, reflector);
-    drop(map);
+        // Starting "All" reflector - shutdown redundant namespaced ones
+        if namespace.is_none() {
+            shutdown_namespaced_reflectors(&mut map, &context, kind, &selector);
+        }
 
-    // Wait for initial sync outside the lock
-    let _ = reader.wait_until_ready().await;
+        let reflector = create_reflector(client, &gvk, &requested_key, metadata_only)?;
+        let started = (reflector.store.clone(), reflector.status.clone());
+        map.insert(requested_key, reflector);
+        started
+    };
+
+    // Wait for initial sync. A forbidden list never becomes ready,
+    // so stop waiting and report it instead of leaving the caller hanging.
+    tokio::select! {
+        ready = reader.wait_until_ready() => ready?,
+        forbidden = status.wait_for(|s| s.state == SyncState::Forbidden) => {
+            if let Ok(s) = forbidden {
+                return Err(s.last_error.clone().unwrap_or_else(|| "forbidden".into()).into());
+            }
+        }
+    }
 
     Ok(())
 }
 
 #[tracing::instrument(skip(client))]
-async fn create_reflector(
+fn create_reflector(
     client: Client,
     gvk: &GroupVersionKind,
-    namespace: Option<String>,
-) -> Result<(ReflectorData, Store<DynamicObject>), Box<dyn std::error::Error>> {
+    key: &ReflectorKey,
+    metadata_only: bool,
+) -> Result<ReflectorData, Box<dyn std::error::Error>> {
     let ar = ApiResource::from_gvk(gvk);
-    let api: Api<DynamicObject> = match &namespace {
+    let api: Api<DynamicObject> = match &key.namespace {
         Some(ns) => Api::namespaced_with(client, ns, &ar),
         None => Api::all_with(client, &ar),
     };
 
-    let config = watcher::Config::default().page_size(10500).timeout(20);
+    let config = key.selector.apply(watcher::Config::default().page_size(10500).timeout(20));
     let writer = Writer::new(ar.clone());
     let reader = writer.as_reader();
 
+    let source = if metadata_only {
+        metadata_watcher(api, config)
+            .map(|res| res.map(metadata_event_to_dynamic))
+            .boxed()
+    } else {
+        watcher(api, config).boxed()
+    };
+
     let cancel = CancellationToken::new();
-    let stream = build_watcher_stream(api, config, &ar, &gvk.kind, cancel.clone(), writer);
+    let (status_tx, status_rx) = watch::channel(ReflectorStatus::default());
+    let changes = Arc::new(Mutex::new(ChangeLog::new()));
+    let (revision_tx, revision_rx) = watch::channel(0);
+    let stream = build_watcher_stream(
+        source,
+        &ar,
+        key.clone(),
+        cancel.clone(),
+        status_tx,
+        (changes.clone(), revision_tx),
+        writer,
+    );
 
     let handle = tokio::spawn(stream.for_each(|_| futures::future::ready(())));
 
-    let data = ReflectorData {
-        store: reader.clone(),
+    Ok(ReflectorData {
+        store: reader,
         handle,
         cancel,
-    };
+        metadata_only,
+        gvk: gvk.clone(),
+        last_access: AtomicI64::new(now_secs()),
+        status: status_rx,
+        changes,
+        revision: revision_rx,
+        lease: Arc::new(()),
+    })
+}
+
+/// Key put into `data` of objects from metadata-only reflectors.
+const METADATA_ONLY_MARKER: &str = "__metadataOnly";
 
-    Ok((data, reader))
+/// Whether `obj` came from a metadata-only reflector, so its spec/status/data are missing.
+pub fn is_metadata_only(obj: &DynamicObject) -> bool {
+    obj.data.get(METADATA_ONLY_MARKER).is_some()
+}
+
+/// Convert a metadata-only watch event into the store's DynamicObject shape.
+/// `types` is cleared so the real kind is filled in instead of PartialObjectMetadata.
+fn metadata_event_to_dynamic(
+    event: Event<PartialObjectMeta<DynamicObject>>,
+) -> Event<DynamicObject> {
+    let convert = |meta: PartialObjectMeta<DynamicObject>| DynamicObject {
+        types: None,
+        metadata: meta.metadata,
+        data: json!({ METADATA_ONLY_MARKER: true }),
+    };
+    match event {
+        Event::Apply(meta) => Event::Apply(convert(meta)),
+        Event::Delete(meta) => Event::Delete(convert(meta)),
+        Event::InitApply(meta) => Event::InitApply(convert(meta)),
+        Event::Init => Event::Init,
+        Event::InitDone => Event::InitDone,
+    }
 }
 
-#[tracing::instrument(skip(api, config, ar, cancel, writer))]
+#[tracing::instrument(skip(source, ar, cancel, status, changes, writer))]
 fn build_watcher_stream(
-    api: Api<DynamicObject>,
-    config: watcher::Config,
+    source: BoxStream<'static, watcher::Result<Event<DynamicObject>>>,
     ar: &ApiResource,
-    kind: &str,
+    key: ReflectorKey,
     cancel: CancellationToken,
+    status: watch::Sender<ReflectorStatus>,
+    (changes, revision): (Arc<Mutex<ChangeLog>>, watch::Sender<u64>),
     writer: Writer<DynamicObject>,
 ) -> impl futures::Stream<Item = ()> {
     let api_version = ar.api_version.clone();
     let ar_kind = ar.kind.clone();
-    let kind_for_events = kind.to_string();
 
-    watcher(api, config)
+    source
         .modify(move |resource| {
             resource.managed_fields_mut().clear();
             resource.data["api_version"] = json!(api_version.clone());
@@ -153,18 +534,106 @@ fn build_watcher_stream(
         })
         .default_backoff()
         .map(move |res| {
-            if let Ok(event) = &res {
-                emit_event(&kind_for_events, event);
+            match &res {
+                Ok(event) => {
+                    track_event(&key, &status, event);
+                    emit_event(&key.context, &key.kind, event);
+                }
+                Err(e) => track_error(&key, &status, e),
             }
             res
         })
         .reflect(writer)
+        // Recorded after the writer applied the event, so a snapshot taken after
+        // reading the log always contains every logged change
+        .inspect(move |res| {
+            if let (Ok(event), Ok(mut log)) = (res, changes.lock()) {
+                match event {
+                    Event::Init | Event::InitDone => log.reset(),
+                    Event::InitApply(_) => {}
+                    Event::Apply(obj) | Event::Delete(obj) => log.record(object_key(obj)),
+                }
+                if !matches!(event, Event::Init | Event::InitApply(_)) {
+                    revision.send_modify(|n| *n += 1);
+                }
+            }
+        })
         .take_until(cancel.cancelled_owned())
         .map(|_| ())
 }
 
+/// Advance the sync state on a successful watch event.
+fn track_event(
+    key: &ReflectorKey,
+    status: &watch::Sender<ReflectorStatus>,
+    event: &Event<DynamicObject>,
+) {
+    let next = match event {
+        Event::Init => SyncState::Initializing,
+        Event::InitApply(_) => return,
+        // Apply/Delete after an error means the watch resumed
+        Event::InitDone | Event::Apply(_) | Event::Delete(_) => SyncState::Synced,
+    };
+    let changed = status.send_if_modified(|s| {
+        if s.state == next {
+            return false;
+        }
+        s.state = next;
+        true
+    });
+    if changed {
+        emit_status(key, &status.borrow());
+    }
+}
+
+/// Record a watch error. The stream keeps retrying with backoff after this.
+fn track_error(key: &ReflectorKey, status: &watch::Sender<ReflectorStatus>, err: &watcher::Error) {
+    tracing::warn!(key.context, key.kind, ns = ?key.namespace, error = %err, "watch error");
+    let next = if is_forbidden(err) {
+        SyncState::Forbidden
+    } else {
+        SyncState::BackingOff
+    };
+    let message = err.to_string();
+    let changed = status.send_if_modified(|s| {
+        if s.state == next && s.last_error.as_deref() == Some(message.as_str()) {
+            return false;
+        }
+        s.state = next;
+        s.last_error = Some(message.clone());
+        true
+    });
+    if changed {
+        emit_status(key, &status.borrow());
+    }
+}
+
+fn is_forbidden(err: &watcher::Error) -> bool {
+    match err {
+        watcher::Error::InitialListFailed(kube::Error::Api(s))
+        | watcher::Error::WatchStartFailed(kube::Error::Api(s))
+        | watcher::Error::WatchFailed(kube::Error::Api(s)) => s.code == 403,
+        watcher::Error::WatchError(s) => s.code == 403,
+        _ => false,
+    }
+}
+
+/// Push a state change to Lua on the `reflector_status` queue name.
+fn emit_status(key: &ReflectorKey, status: &ReflectorStatus) {
+    let payload = json!({
+        "context": key.context,
+        "kind": key.kind,
+        "namespace": key.namespace,
+        "state": status.state,
+        "last_error": status.last_error,
+    });
+    if let Ok(payload_str) = serde_json::to_string(&payload) {
+        let _ = notify_named("reflector_status", payload_str);
+    }
+}
+
 #[tracing::instrument(skip(event))]
-fn emit_event(kind: &str, event: &Event<DynamicObject>) {
+fn emit_event(context: &str, kind: &str, event: &Event<DynamicObject>) {
     let (event_type, metadata) = match event {
         Event::Apply(obj) => ("MODIFIED", Some(&obj.metadata)),
         Event::Delete(obj) => ("DELETED", Some(&obj.metadata)),
@@ -173,6 +642,7 @@ fn emit_event(kind: &str, event: &Event<DynamicObject>) {
 
     let payload = json!({
         "event": event_type,
+        "context": context,
         "metadata": metadata.and_then(|m| serde_json::to_value(m).ok())
     });
 
@@ -181,32 +651,121 @@ fn emit_event(kind: &str, event: &Event<DynamicObject>) {
     }
 }
 
-#[tracing::instrument]
+/// Cached objects for `kind` in the active context.
 pub fn get(kind: &str, namespace: Option<String>) -> Result<Vec<Arc<DynamicObject>>, mlua::Error> {
+    get_in_context(&active_context(), kind, namespace, &WatchSelector::default())
+}
+
+#[tracing::instrument]
+pub fn get_in_context(
+    context: &str,
+    kind: &str,
+    namespace: Option<String>,
+    selector: &WatchSelector,
+) -> Result<Vec<Arc<DynamicObject>>, mlua::Error> {
+    get_changes_in_context(context, kind, namespace, selector, None).map(|delta| delta.objects)
+}
+
+/// Like [`get_in_context`], plus the keys of objects changed since `cursor`.
+/// Pass the returned cursor back on the next call to receive only newer changes.
+#[tracing::instrument]
+pub fn get_changes_in_context(
+    context: &str,
+    kind: &str,
+    namespace: Option<String>,
+    selector: &WatchSelector,
+    cursor: Option<ChangeCursor>,
+) -> Result<StoreDelta, mlua::Error> {
     let map = store_map()
         .read()
         .map_err(|_| mlua::Error::RuntimeError("STORE_MAP lock poisoned".into()))?;
 
-    let data = map
-        .get(&key(kind, None))
-        .or_else(|| map.get(&key(kind, namespace.as_deref())));
+    // Prefer a full reflector when both a metadata-only and a full one could answer
+    let candidates: Vec<&ReflectorData> = [
+        key(context, kind, None, selector),
+        key(context, kind, namespace.as_deref(), selector),
+    ]
+    .iter()
+    .filter_map(|k| map.get(k))
+    .collect();
+    let data = candidates
+        .iter()
+        .find(|data| !data.metadata_only)
+        .or_else(|| candidates.first());
 
     let Some(data) = data else {
-        return Ok(Vec::new());
+        drop(map);
+        revive_evicted(context, kind, namespace.as_deref(), selector);
+        return Ok(StoreDelta::default());
+    };
+    data.touch();
+
+    // Read the log before the snapshot: anything logged is already in the store
+    let (changed, cursor) = match data.changes.lock() {
+        Ok(log) => (log.since(cursor), Some(log.cursor())),
+        Err(_) => (None, None),
     };
 
-    let result = data
+    let objects = data
         .store
         .state()
         .into_iter()
         .filter(|obj| matches_namespace(obj, namespace.as_deref()))
         .collect();
 
-    Ok(result)
+    Ok(StoreDelta {
+        objects,
+        changed,
+        cursor,
+    })
+}
+
+/// Start or reuse the full reflector for `{context, kind, namespace, selector}` and
+/// subscribe to it. Resolves once the store holds the initial list.
+pub async fn subscribe(
+    context: String,
+    gvk: GroupVersionKind,
+    namespace: Option<String>,
+    selector: WatchSelector,
+) -> Result<Subscription, String> {
+    let client = context_client(&context).await.map_err(|e| e.to_string())?;
+    let kind = gvk.kind.clone();
+
+    init_reflector_for_kind(
+        context.clone(),
+        client,
+        gvk,
+        namespace.clone(),
+        selector.clone(),
+        false,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let map = store_map().read().map_err(|_| "STORE_MAP lock poisoned")?;
+    let data = [
+        key(&context, &kind, None, &selector),
+        key(&context, &kind, namespace.as_deref(), &selector),
+    ]
+    .iter()
+    .filter_map(|k| map.get(k))
+    .find(|data| !data.metadata_only)
+    .ok_or_else(|| format!("{kind} reflector stopped while starting"))?;
+
+    Ok(Subscription {
+        store: data.store.clone(),
+        namespace,
+        revision: data.revision.clone(),
+        status: data.status.clone(),
+        _lease: data.lease.clone(),
+    })
 }
 
+/// Look up a single object in `context`.
+/// Any reflector for the kind can answer, including ones narrowed by a selector.
 #[tracing::instrument]
-pub fn get_single(
+pub fn get_single_in_context(
+    context: &str,
     kind: &str,
     namespace: Option<String>,
     name: &str,
@@ -215,17 +774,35 @@ pub fn get_single(
         .read()
         .map_err(|_| mlua::Error::RuntimeError("STORE_MAP lock poisoned".into()))?;
 
-    let data = map
-        .get(&key(kind, None))
-        .or_else(|| map.get(&key(kind, namespace.as_deref())))
-        .ok_or_else(|| mlua::Error::RuntimeError("No store found for kind".into()))?;
-
-    let result = data
-        .store
-        .state()
+    let mut candidates: Vec<_> = map
         .iter()
-        .find(|obj| obj.name_any() == name && matches_namespace(obj, namespace.as_deref()))
-        .map(|obj| obj.as_ref().clone());
+        .filter(|(k, _)| {
+            k.context == context
+                && k.kind == kind
+                && (k.namespace.is_none() || k.namespace == namespace)
+        })
+        .collect();
+
+    // No reflector (never started or evicted): callers fetch from the API instead
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    // Metadata-only stores can't answer a full lookup; returning None makes
+    // callers fetch the full object from the API on demand.
+    candidates.retain(|(_, data)| !data.metadata_only);
+
+    // Prefer unfiltered reflectors, they are the most likely to hold the object
+    candidates.sort_by_key(|(k, _)| !k.selector.is_empty());
+
+    let result = candidates.into_iter().find_map(|(_, data)| {
+        data.touch();
+        data.store
+            .state()
+            .iter()
+            .find(|obj| obj.name_any() == name && matches_namespace(obj, namespace.as_deref()))
+            .map(|obj| obj.as_ref().clone())
+    });
 
     Ok(result)
 }
@@ -237,3 +814,228 @@ fn matches_namespace(obj: &DynamicObject, namespace: Option<&str>) -> bool {
         (Some(obj_ns), Some(ns)) => obj_ns == ns, // Specific namespace match
     }
 }
+
+/// Addresses of all cached objects and the contexts that still have reflectors,
+/// so caches derived from them can drop what is gone.
+pub fn live_objects() -> (HashSet<usize>, HashSet<String>) {
+    let Ok(map) = store_map().read() else {
+        return (HashSet::new(), HashSet::new());
+    };
+    let mut objects = HashSet::new();
+    let mut contexts = HashSet::new();
+    for (k, data) in map.iter() {
+        contexts.insert(k.context.clone());
+        objects.extend(data.store.state().iter().map(|obj| Arc::as_ptr(obj) as usize));
+    }
+    (objects, contexts)
+}
+
+/// Set the idle TTL and global object budget, and (re)start the background sweeper.
+/// Zero disables the corresponding limit.
+pub fn configure_lifecycle(idle_ttl_secs: u64, max_objects: usize) {
+    IDLE_TTL_SECS.store(idle_ttl_secs, Ordering::Relaxed);
+    OBJECT_BUDGET.store(max_objects, Ordering::Relaxed);
+
+    let Ok(mut sweeper) = SWEEPER.lock() else {
+        tracing::warn!("SWEEPER lock poisoned");
+        return;
+    };
+    if let Some(handle) = sweeper.take() {
+        handle.abort();
+    }
+    if idle_ttl_secs == 0 && max_objects == 0 {
+        return;
+    }
+    if let Some(rt) = RUNTIME.get() {
+        *sweeper = Some(rt.spawn(async {
+            let mut tick = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                tick.tick().await;
+                evict_reflectors();
+            }
+        }));
+    }
+}
+
+/// Start a reflector again that was evicted while its view was hidden or closed.
+/// The read that noticed it gets an empty result; the next refresh sees the data.
+fn revive_evicted(context: &str, kind: &str, namespace: Option<&str>, selector: &WatchSelector) {
+    let revived = evicted_map().lock().ok().and_then(|mut evicted| {
+        let all = evicted.remove_entry(&key(context, kind, None, selector));
+        all.or_else(|| evicted.remove_entry(&key(context, kind, namespace, selector)))
+    });
+    let (Some((key, (gvk, metadata_only))), Some(rt)) = (revived, RUNTIME.get()) else {
+        return;
+    };
+    tracing::debug!(key.context, key.kind, ns = ?key.namespace, "Restarting evicted reflector");
+    respawn(rt, key, gvk, metadata_only);
+}
+
+/// Stop every reflector and start it again with a freshly built client, so a
+/// reloaded kubeconfig (new credentials, server or proxy) reaches running watches.
+pub fn restart_reflectors() {
+    let stopped: Vec<(ReflectorKey, GroupVersionKind, bool)> = match store_map().write() {
+        Ok(mut map) => map
+            .drain()
+            .map(|(key, data)| {
+                let (gvk, metadata_only) = (data.gvk.clone(), data.metadata_only);
+                data.shutdown();
+                (key, gvk, metadata_only)
+            })
+            .collect(),
+        Err(_) => return tracing::warn!("STORE_MAP lock poisoned during restart"),
+    };
+    let Some(rt) = RUNTIME.get() else {
+        return;
+    };
+    for (key, gvk, metadata_only) in stopped {
+        tracing::debug!(key.context, key.kind, ns = ?key.namespace, "Restarting reflector");
+        respawn(rt, key, gvk, metadata_only);
+    }
+}
+
+fn respawn(rt: &Runtime, key: ReflectorKey, gvk: GroupVersionKind, metadata_only: bool) {
+    rt.spawn(async move {
+        let client = match context_client(&key.context).await {
+            Ok(client) => client,
+            Err(e) => return tracing::warn!(key.context, "restart reflector: {e}"),
+        };
+        let ReflectorKey {
+            context,
+            namespace,
+            selector,
+            ..
+        } = key;
+        let started =
+            init_reflector_for_kind(context, client, gvk, namespace, selector, metadata_only)
+                .await
+                .map_err(|e| e.to_string());
+        if let Err(e) = started {
+            tracing::warn!("restart reflector: {e}");
+        }
+    });
+}
+
+/// Drop reflectors idle past the TTL, then the least recently used ones until
+/// the total object count fits the budget. Reflectors read within `IN_USE_SECS`
+/// belong to open views and are kept, as are reflectors with live subscriptions.
+/// Returns the number evicted.
+#[tracing::instrument]
+pub fn evict_reflectors() -> usize {
+    let ttl = IDLE_TTL_SECS.load(Ordering::Relaxed) as i64;
+    let budget = OBJECT_BUDGET.load(Ordering::Relaxed);
+
+    let Ok(mut map) = store_map().write() else {
+        tracing::warn!("STORE_MAP lock poisoned during eviction");
+        return 0;
+    };
+
+    let mut evicted: Vec<ReflectorKey> = Vec::new();
+    if ttl > 0 {
+        evicted.extend(
+            map.iter()
+                .filter(|(_, data)| !data.subscribed() && data.idle_secs() >= ttl.max(IN_USE_SECS))
+                .map(|(k, _)| k.clone()),
+        );
+    }
+
+    if budget > 0 {
+        let mut total: usize = map
+            .iter()
+            .filter(|(k, _)| !evicted.contains(k))
+            .map(|(_, data)| data.store.len())
+            .sum();
+        let mut remaining: Vec<(ReflectorKey, i64, usize)> = map
+            .iter()
+            .filter(|(k, data)| {
+                !evicted.contains(k) && !data.subscribed() && data.idle_secs() >= IN_USE_SECS
+            })
+            .map(|(k, data)| (k.clone(), data.last_access(), data.store.len()))
+            .collect();
+
+        // Oldest first
+        remaining.sort_by_key(|(_, last_access, _)| *last_access);
+
+        for (k, _, len) in remaining {
+            if total <= budget {
+                break;
+            }
+            total -= len;
+            evicted.push(k);
+        }
+    }
+
+    let mut revivable = evicted_map().lock().ok();
+    for k in &evicted {
+        if let Some(data) = map.remove(k) {
+            tracing::debug!(k.context, k.kind, ns = ?k.namespace, "Evicting reflector");
+            if let Some(revivable) = revivable.as_mut() {
+                revivable.insert(k.clone(), (data.gvk.clone(), data.metadata_only));
+            }
+            data.shutdown();
+        }
+    }
+    drop(revivable);
+    drop(map);
+
+    if !evicted.is_empty() {
+        release_stale_caches();
+    }
+    evicted.len()
+}
+
+pub fn install(lua: &Lua, exports: &LuaTable) -> LuaResult<()> {
+    let store_stats = lua.create_function(|lua, ()| {
+        let map = store_map()
+            .read()
+            .map_err(|_| mlua::Error::RuntimeError("STORE_MAP lock poisoned".into()))?;
+
+        let out = lua.create_table()?;
+        for (i, (k, data)) in map.iter().enumerate() {
+            let row = lua.create_table()?;
+            row.set("context", k.context.clone())?;
+            row.set("kind", k.kind.clone())?;
+            row.set("namespace", k.namespace.clone())?;
+            row.set("label_selector", k.selector.labels.clone())?;
+            row.set("field_selector", k.selector.fields.clone())?;
+            row.set("metadata_only", data.metadata_only)?;
+            row.set("objects", data.store.len())?;
+            let status = data.status.borrow();
+            row.set("state", status.state.as_str())?;
+            row.set("last_error", status.last_error.clone())?;
+            row.set("idle_secs", data.idle_secs())?;
+            out.set(i + 1, row)?;
+        }
+        Ok(out)
+    })?;
+
+    let configure_store = lua.create_function(|_, (idle_ttl, max_objects): (u64, usize)| {
+        configure_lifecycle(idle_ttl, max_objects);
+        Ok(())
+    })?;
+
+    let reflector_status = lua.create_function(|lua, json: String| {
+        let args: ReflectorStatusArgs = serde_json::from_str(&json)
+            .map_err(|e| mlua::Error::external(format!("bad json: {e}")))?;
+        let context = args.context.unwrap_or_else(active_context);
+        let selector = WatchSelector::new(args.label_selector, args.field_selector);
+
+        let map = store_map()
+            .read()
+            .map_err(|_| mlua::Error::RuntimeError("STORE_MAP lock poisoned".into()))?;
+        let status = map
+            .get(&key(&context, &args.gvk.k, None, &selector))
+            .or_else(|| map.get(&key(&context, &args.gvk.k, args.namespace.as_deref(), &selector)))
+            .map(|data| data.status.borrow().clone());
+
+        match status {
+            Some(status) => lua.to_value(&status),
+            None => Ok(mlua::Value::Nil),
+        }
+    })?;
+
+    exports.set("store_stats", store_stats)?;
+    exports.set("reflector_status", reflector_status)?;
+    exports.set("configure_store", configure_store)?;
+    Ok(())
+}
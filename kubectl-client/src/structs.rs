@@ -1,5 +1,6 @@
 use mlua::{FromLua, Lua, Result as LuaResult, Value as LuaValue};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Gvk {
@@ -12,6 +13,9 @@ pub struct Gvk {
 pub struct GetAllArgs {
     pub gvk: Gvk,
     pub namespace: Option<String>,
+    pub context: Option<String>,
+    pub label_selector: Option<String>,
+    pub field_selector: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -23,12 +27,49 @@ pub struct GetTableArgs {
     pub filter: Option<String>,
     pub filter_label: Option<Vec<String>>,
     pub filter_key: Option<String>,
+    pub context: Option<String>,
+    /// Server-side selectors; must match the ones the reflector was started with
+    pub label_selector: Option<String>,
+    pub field_selector: Option<String>,
+    /// When set, rows for the kind are merged from all of these contexts
+    pub contexts: Option<Vec<String>>,
+    /// Return only this window of the sorted, filtered rows plus the total count
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    /// `name` or `namespace/name` whose 1-based row index is returned as `index`
+    pub locate: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetTableDeltaArgs {
+    /// Caller-chosen id; rows from the previous call with the same id are reused
+    pub session: String,
+    /// Send every visible row instead of a diff, e.g. when the caller lost its rows
+    pub full: Option<bool>,
+    #[serde(flatten)]
+    pub table: GetTableArgs,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct StartReflectorArgs {
     pub gvk: Gvk,
     pub namespace: Option<String>,
+    pub context: Option<String>,
+    /// Label selector passed to the watch, e.g. "app=checkout"
+    pub label_selector: Option<String>,
+    /// Field selector passed to the watch, e.g. "status.phase=Running"
+    pub field_selector: Option<String>,
+    /// Watch PartialObjectMetadata only (name, namespace, labels, age), no payload
+    pub metadata_only: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReflectorStatusArgs {
+    pub gvk: Gvk,
+    pub namespace: Option<String>,
+    pub context: Option<String>,
+    pub label_selector: Option<String>,
+    pub field_selector: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -43,11 +84,15 @@ pub struct GetSingleArgs {
     pub namespace: Option<String>,
     pub output: Option<String>,
     pub cached: Option<bool>,
+    /// Context the object lives in, for rows of aggregated tables; defaults to the active one
+    pub context: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CmdEditArgs {
     pub path: String,
+    /// Context to apply the edit in; defaults to the active one
+    pub context: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -55,6 +100,8 @@ pub struct CmdDeleteArgs {
     pub gvk: Gvk,
     pub name: String,
     pub namespace: Option<String>,
+    /// Context to delete in; defaults to the active one
+    pub context: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -81,6 +128,8 @@ pub struct GetFallbackTableArgs {
     pub filter: Option<String>,
     pub filter_label: Option<Vec<String>>,
     pub filter_key: Option<String>,
+    /// When set, rows are merged from the cached objects of all of these contexts
+    pub contexts: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -101,6 +150,24 @@ pub struct CmdDescribeArgs {
 pub struct PodRef {
     pub name: String,
     pub namespace: String,
+    /// Job run the pod belongs to; shown instead of the pod name in line prefixes
+    #[serde(default)]
+    pub run: Option<String>,
+}
+
+/// Pods to log by label selector or owning workload instead of a fixed list.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct LogSelector {
+    pub namespace: String,
+    /// Label selector such as "app=web,tier!=cache"
+    pub labels: Option<String>,
+    /// Workload kind (Deployment, StatefulSet, DaemonSet, ReplicaSet, Job) whose selector is used,
+    /// or CronJob for the pods of its recent runs
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    /// CronJob runs to include, newest last (default 3)
+    pub runs: Option<usize>,
 }
 
 /// Unified configuration for log streaming and fetching.
@@ -122,25 +189,68 @@ pub struct LogConfig {
     pub prefix: Option<bool>,
     /// If true, streams continuously; if false, one-shot fetch
     pub follow: Option<bool>,
+    /// Start from the last N lines of each container instead of the full log or `since`
+    pub tail_lines: Option<i64>,
+    /// Stop reading each container after this many bytes; limited follows do not resume
+    pub limit_bytes: Option<i64>,
+    /// Lines a streaming session keeps for `search` and `context`
+    pub buffer_lines: Option<usize>,
     /// Number of histogram buckets (for one-shot fetch display)
     pub histogram_width: Option<usize>,
+    /// Histogram rows: "level" (error/warn/info/other), "pod", or a single total row
+    pub histogram_split: Option<String>,
+    /// One-shot fetch returns `{ histogram, lines }` with bucket data instead of a line array
+    pub histogram_data: Option<bool>,
+    /// One-shot fetch: merge containers by timestamp instead of arrival order.
+    /// Timestamps are requested for ordering and stripped afterwards unless `timestamps` is set
+    pub ordered: Option<bool>,
+    /// Resolve pods from a selector; in follow mode pods are attached and
+    /// detached as they come and go
+    pub selector: Option<LogSelector>,
+    /// Only keep lines matching this regex; matches are reported as highlight spans
+    pub include: Option<String>,
+    /// Drop lines matching this regex
+    pub exclude: Option<String>,
+    /// Minimum level ("debug", "info", "warn", "error", ...) for lines with a recognized level
+    pub level: Option<String>,
+    /// Parse JSON and logfmt lines into records with timestamp, level and msg
+    pub structured: Option<bool>,
+    /// Extra keys to extract into record fields; nested JSON keys use dots ("http.status")
+    pub fields: Option<Vec<String>>,
+    /// Keep only structured lines whose field matches the regex, keyed by field name
+    pub field_filters: Option<HashMap<String, String>>,
 }
 
 impl FromLua for LogConfig {
     fn from_lua(value: LuaValue, _lua: &Lua) -> LuaResult<Self> {
         match value {
             LuaValue::Table(t) => {
-                let pods: Vec<mlua::Table> = t.get("pods")?;
+                let pods: Option<Vec<mlua::Table>> = t.get("pods")?;
                 let pods = pods
+                    .unwrap_or_default()
                     .into_iter()
                     .map(|p| {
                         Ok(PodRef {
                             name: p.get("name")?,
                             namespace: p.get("namespace")?,
+                            run: p.get("run")?,
                         })
                     })
                     .collect::<LuaResult<Vec<_>>>()?;
 
+                let selector: Option<mlua::Table> = t.get("selector")?;
+                let selector = selector
+                    .map(|s| {
+                        Ok::<_, mlua::Error>(LogSelector {
+                            namespace: s.get("namespace")?,
+                            labels: s.get("labels")?,
+                            kind: s.get("kind")?,
+                            name: s.get("name")?,
+                            runs: s.get("runs")?,
+                        })
+                    })
+                    .transpose()?;
+
                 Ok(LogConfig {
                     pods,
                     container: t.get("container")?,
@@ -150,6 +260,19 @@ impl FromLua for LogConfig {
                     prefix: t.get("prefix")?,
                     follow: t.get("follow")?,
                     histogram_width: t.get("histogram_width")?,
+                    histogram_split: t.get("histogram_split")?,
+                    histogram_data: t.get("histogram_data")?,
+                    ordered: t.get("ordered")?,
+                    tail_lines: t.get("tail_lines")?,
+                    limit_bytes: t.get("limit_bytes")?,
+                    buffer_lines: t.get("buffer_lines")?,
+                    selector,
+                    include: t.get("include")?,
+                    exclude: t.get("exclude")?,
+                    level: t.get("level")?,
+                    structured: t.get("structured")?,
+                    fields: t.get("fields")?,
+                    field_filters: t.get("field_filters")?,
                 })
             }
             _ => Err(mlua::Error::FromLuaConversionError {
@@ -161,6 +284,42 @@ impl FromLua for LogConfig {
     }
 }
 
+/// Destination and file handling for `log_export_start`.
+#[derive(Debug, Clone, Default)]
+pub struct LogExportOptions {
+    pub path: String,
+    /// "plain" (default) or "jsonl" with timestamp/pod/container/message fields
+    pub format: Option<String>,
+    /// Compress files with gzip
+    pub gzip: Option<bool>,
+    /// Rotate once the current file holds this many uncompressed bytes
+    pub max_bytes: Option<u64>,
+    /// Rotated files kept as `path.1` .. `path.N` (default 5, at least 1)
+    pub max_files: Option<usize>,
+    /// Stop after a duration like "30m"; runs until stopped when unset
+    pub duration: Option<String>,
+}
+
+impl FromLua for LogExportOptions {
+    fn from_lua(value: LuaValue, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Table(t) => Ok(LogExportOptions {
+                path: t.get("path")?,
+                format: t.get("format")?,
+                gzip: t.get("gzip")?,
+                max_bytes: t.get("max_bytes")?,
+                max_files: t.get("max_files")?,
+                duration: t.get("duration")?,
+            }),
+            _ => Err(mlua::Error::FromLuaConversionError {
+                from: value.type_name(),
+                to: "LogExportOptions".to_string(),
+                message: Some("expected table".to_string()),
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ImageSpec {
     pub name: String,
@@ -185,3 +344,104 @@ impl FromLua for ImageSpec {
         }
     }
 }
+
+/// Remote side of a port mapping: a number, or a container/service port name
+/// resolved through the pod spec.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemotePort {
+    Number(u16),
+    Name(String),
+}
+
+impl<'de> Deserialize<'de> for RemotePort {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u16),
+            Text(String),
+        }
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Number(port) => RemotePort::Number(port),
+            Raw::Text(s) => match s.trim().parse() {
+                Ok(port) => RemotePort::Number(port),
+                Err(_) => RemotePort::Name(s.trim().to_string()),
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PortRequest {
+    /// 0 picks a free port
+    #[serde(default)]
+    pub local_port: u16,
+    pub remote_port: RemotePort,
+}
+
+impl FromLua for RemotePort {
+    fn from_lua(value: LuaValue, lua: &Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::String(s) => {
+                let s = s.to_str()?.trim().to_string();
+                Ok(match s.parse() {
+                    Ok(port) => RemotePort::Number(port),
+                    Err(_) => RemotePort::Name(s),
+                })
+            }
+            other => Ok(RemotePort::Number(u16::from_lua(other, lua)?)),
+        }
+    }
+}
+
+impl FromLua for PortRequest {
+    fn from_lua(value: LuaValue, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Table(t) => Ok(PortRequest {
+                local_port: t.get::<Option<u16>>("local_port")?.unwrap_or(0),
+                remote_port: t.get("remote_port")?,
+            }),
+            _ => Err(mlua::Error::FromLuaConversionError {
+                from: value.type_name(),
+                to: "PortRequest".to_string(),
+                message: Some("expected table".to_string()),
+            }),
+        }
+    }
+}
+
+/// One forward of a `portforward_start_all` batch.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PortForwardStartArgs {
+    /// Context to forward in, independent of the active one
+    pub context: String,
+    /// "pod" or "service"
+    pub kind: String,
+    pub name: String,
+    pub namespace: String,
+    pub host: String,
+    pub ports: Vec<PortRequest>,
+    #[serde(default)]
+    pub round_robin: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PortForwardOptions {
+    /// Services only: rotate new connections over all ready endpoints
+    pub round_robin: bool,
+}
+
+impl FromLua for PortForwardOptions {
+    fn from_lua(value: LuaValue, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Table(t) => Ok(PortForwardOptions {
+                round_robin: t.get::<Option<bool>>("round_robin")?.unwrap_or(false),
+            }),
+            _ => Err(mlua::Error::FromLuaConversionError {
+                from: value.type_name(),
+                to: "PortForwardOptions".to_string(),
+                message: Some("expected table".to_string()),
+            }),
+        }
+    }
+}
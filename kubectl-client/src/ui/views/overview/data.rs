@@ -5,7 +5,7 @@
 use kube::api::{DynamicObject, GroupVersionKind, ResourceExt};
 use tokio::{runtime::Handle, task};
 
-use crate::{store, with_client};
+use crate::{store, with_context_client};
 
 /// Namespace information for display.
 #[derive(Clone)]
@@ -51,22 +51,28 @@ fn ensure_reflectors() {
     block_on(async {
         // Initialize Namespace reflector (core/v1)
         let ns_gvk = GroupVersionKind::gvk("", "v1", "Namespace");
-        let _ = with_client(|client| async move {
-            store::init_reflector_for_kind(client.clone(), ns_gvk, None).await.ok();
+        let _ = with_context_client(None, |context, client| async move {
+            store::init_reflector_for_kind(context, client, ns_gvk, None, Default::default(), false)
+                .await
+                .ok();
             Ok::<(), mlua::Error>(())
         });
 
         // Initialize Event reflector (events.k8s.io/v1)
         let event_gvk = GroupVersionKind::gvk("events.k8s.io", "v1", "Event");
-        let _ = with_client(|client| async move {
-            store::init_reflector_for_kind(client.clone(), event_gvk, None).await.ok();
+        let _ = with_context_client(None, |context, client| async move {
+            store::init_reflector_for_kind(context, client, event_gvk, None, Default::default(), false)
+                .await
+                .ok();
             Ok::<(), mlua::Error>(())
         });
 
         // Initialize Pod reflector for stats (core/v1)
         let pod_gvk = GroupVersionKind::gvk("", "v1", "Pod");
-        let _ = with_client(|client| async move {
-            store::init_reflector_for_kind(client.clone(), pod_gvk, None).await.ok();
+        let _ = with_context_client(None, |context, client| async move {
+            store::init_reflector_for_kind(context, client, pod_gvk, None, Default::default(), false)
+                .await
+                .ok();
             Ok::<(), mlua::Error>(())
         });
     });
@@ -41,7 +41,7 @@ pub fn pad_key(n: usize) -> String {
     format!("{:020}", n)
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct FieldValue {
     pub value: String,
     pub symbol: Option<String>,
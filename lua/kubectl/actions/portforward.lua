@@ -49,6 +49,27 @@ local function extract_service_ports(spec)
   return ports
 end
 
+--- Pair comma separated local and remote ports; a missing local port is 0 (any free port)
+---@param locals string e.g. "8080,0"
+---@param remotes string e.g. "http,grpc" or "80"
+---@return { local_port: integer, remote_port: string }[]|nil mappings
+---@return string|nil err
+local function parse_port_mappings(locals, remotes)
+  local local_list = vim.split(locals, ",", { trimempty = true })
+  local mappings = {}
+  for i, remote in ipairs(vim.split(remotes, ",", { trimempty = true })) do
+    local local_port = tonumber(vim.trim(local_list[i] or "0"))
+    if not local_port then
+      return nil, "invalid local port: " .. local_list[i]
+    end
+    table.insert(mappings, { local_port = local_port, remote_port = vim.trim(remote) })
+  end
+  if #mappings == 0 then
+    return nil, "no container port given"
+  end
+  return mappings
+end
+
 local port_extractors = {
   pod = extract_pod_ports,
   service = extract_service_ports,
@@ -113,13 +134,38 @@ function M.portforward(resource_type, gvk, name, ns)
           hl = hl.symbols.pending,
         },
       }
+      if resource_type == "service" then
+        table.insert(pf_data, {
+          text = "balance:",
+          value = "first",
+          options = { "first", "round-robin" },
+          cmd = "",
+          type = "positional",
+          hl = hl.symbols.pending,
+        })
+      end
 
       builder.action_view(def, pf_data, function(args)
         local client = require("kubectl.client")
         local address = args[1].value
-        local local_port = args[2].value
-        local remote_port = args[3].value
-        client.portforward_start(gvk.k, name, ns, address, local_port, remote_port)
+        local mappings, err = parse_port_mappings(args[2].value, args[3].value)
+        if not mappings then
+          vim.notify("Port forward failed: " .. err, vim.log.levels.ERROR)
+          return
+        end
+
+        local opts = { round_robin = args[4] and args[4].value == "round-robin" }
+        local ok, id, ports = pcall(client.portforward_start, gvk.k, name, ns, address, mappings, opts)
+        if not ok then
+          vim.notify("Port forward failed: " .. tostring(id), vim.log.levels.ERROR)
+          return
+        end
+        local bound = {}
+        for _, port in ipairs(ports) do
+          local remote = port.name and (port.name .. "(" .. port.remote_port .. ")") or port.remote_port
+          table.insert(bound, string.format("%s:%d -> %s", address, port.local_port, remote))
+        end
+        vim.notify("Port forward " .. id .. ": " .. table.concat(bound, ", "))
       end)
     end)
   end)
@@ -1,4 +1,5 @@
 local commands = require("kubectl.actions.commands")
+local config = require("kubectl.config")
 local state = require("kubectl.state")
 
 --- @class kubectl.Client
@@ -11,6 +12,8 @@ function client.set_implementation(callback)
   client.implementation = require("kubectl_client")
   client.implementation.init_logging(vim.fn.stdpath("log"))
   client.implementation.init_runtime(state.context["current-context"])
+  local store_opts = config.options.store
+  client.implementation.configure_store(store_opts.idle_ttl, store_opts.max_objects)
   commands.run_async("init_client_async", {}, function(ok)
     if ok then
       client.implementation.init_metrics()
@@ -43,6 +46,10 @@ function client.portforward_list()
   return client.implementation.portforward_list()
 end
 
+function client.portforward_detail(id)
+  return client.implementation.portforward_detail(id)
+end
+
 function client.portforward_stop(id)
   return client.implementation.portforward_stop(id)
 end
@@ -96,14 +103,35 @@ function client.node_shell(config)
   return client.implementation.node_shell(config)
 end
 
+--- @class kubectl.LogSelector
+--- @field namespace string
+--- @field labels? string Label selector, e.g. "app=web"
+--- @field kind? string Deployment, StatefulSet, DaemonSet, ReplicaSet, Job or CronJob
+--- @field name? string Workload name, used with kind
+--- @field runs? integer CronJob runs to include (default 3); job pods are prefixed by run
+
 --- @class kubectl.LogConfig
---- @field pods table[] Array of {name, namespace} tables
+--- @field pods? table[] Array of {name, namespace} tables
+--- @field selector? kubectl.LogSelector Follow pods by selector or workload instead of `pods`
 --- @field container? string Target container name
 --- @field timestamps? boolean Include timestamps in output
 --- @field since? string Duration like "5m", "1h"
---- @field follow? boolean Stream continuously
+--- @field follow? boolean Stream continuously; follows reconnect after stream errors and resume where they stopped
+--- @field tail_lines? integer Start from the last N lines of each container
+--- @field buffer_lines? integer Lines a follow session keeps for search and context (default 250000)
+--- @field limit_bytes? integer Stop each container's stream after this many bytes (limited follows do not reconnect)
 --- @field previous? boolean Fetch from previous container instance
 --- @field prefix? boolean Force prefix behavior
+--- @field histogram_width? integer Histogram width in columns (one-shot fetch)
+--- @field histogram_split? "total"|"level"|"pod" One histogram row per level or pod
+--- @field histogram_data? boolean One-shot fetch returns { histogram, lines } with bucket data
+--- @field ordered? boolean One-shot fetch: merge pods by timestamp; timestamps are stripped unless `timestamps` is set
+--- @field include? string Only keep lines matching this regex
+--- @field exclude? string Drop lines matching this regex
+--- @field level? string Minimum level: trace, debug, info, warn, error or fatal
+--- @field structured? boolean Parse JSON/logfmt lines into records (see read_records)
+--- @field fields? string[] Extra record keys; nested JSON keys use dots
+--- @field field_filters? table<string, string> Field name to regex; only matching structured lines are kept
 
 --- Create a log streaming session
 --- @param config kubectl.LogConfig
@@ -112,6 +140,45 @@ function client.log_session(config)
   return client.implementation.log_session(config)
 end
 
+--- @class kubectl.LogExportOptions
+--- @field path string Destination file
+--- @field format? "plain"|"jsonl" jsonl writes {timestamp, pod, container, message} objects
+--- @field gzip? boolean Compress with gzip
+--- @field max_bytes? integer Rotate to path.1 .. path.N after this many uncompressed bytes
+--- @field max_files? integer Rotated files to keep (default 5, at least 1)
+--- @field duration? string Stop after e.g. "30m"
+
+--- Write a log stream to a file in the background, independent of any buffer
+--- @param config kubectl.LogConfig Follows unless `follow` is false
+--- @param options kubectl.LogExportOptions
+--- @return integer id
+function client.log_export_start(config, options)
+  return client.implementation.log_export_start(config, options)
+end
+
+--- @class kubectl.LogExport
+--- @field id integer
+--- @field path string
+--- @field format string
+--- @field gzip boolean
+--- @field started string
+--- @field status "running"|"done"|"failed"
+--- @field lines integer
+--- @field bytes integer Uncompressed bytes written
+--- @field rotations integer
+--- @field error? string
+
+--- @return table<integer, kubectl.LogExport>
+function client.log_export_list()
+  return client.implementation.log_export_list()
+end
+
+--- Stop an export, flushing lines received so far, and remove it from the list
+--- @param id integer
+function client.log_export_stop(id)
+  return client.implementation.log_export_stop(id)
+end
+
 --- @class kubectl.DescribeConfig
 --- @field name string Resource name
 --- @field namespace? string Namespace
@@ -143,6 +210,31 @@ function client.get_drift(path, hide_unchanged)
   return client.implementation.get_drift(path, hide_unchanged)
 end
 
+--- Forget the rows kept for an incremental table session
+--- @param session string
+function client.drop_table_session(session)
+  return client.implementation.drop_table_session(session)
+end
+
+--- List active reflectors with their size and sync state
+--- @return kubectl.StoreStat[]
+function client.store_stats()
+  return client.implementation.store_stats()
+end
+
+--- Sync state and last watch error of the reflector serving a kind
+--- @param gvk { g: string, v: string, k: string }
+--- @param namespace? string
+--- @param context? string
+--- @return kubectl.ReflectorStatus?
+function client.reflector_status(gvk, namespace, context)
+  return client.implementation.reflector_status(vim.json.encode({
+    gvk = gvk,
+    namespace = namespace,
+    context = context,
+  }))
+end
+
 function client.setup_queue()
   return client.implementation.setup_queue()
 end
@@ -9,6 +9,27 @@
 --- @field open fun(self: kubectl.LogSession): boolean
 --- @field close fun(self: kubectl.LogSession)
 --- @field read_chunk fun(self: kubectl.LogSession): string[]?
+--- @field read_chunk_with_spans fun(self: kubectl.LogSession): { lines: string[], spans: integer[][][] }?
+--- @field read_records fun(self: kubectl.LogSession): kubectl.LogRecord[]?
+--- @field context fun(self: kubectl.LogSession, line: integer, n?: integer): { line: integer, text: string }[]
+--- @field id fun(self: kubectl.LogSession): integer Key for log_search_async
+
+--- @class kubectl.LogSearchResult
+--- @field first integer Oldest retained line; lines are numbered from 1 in delivery order
+--- @field last integer
+--- @field total integer Matches over all retained lines
+--- @field lines integer Lines with at least one match
+--- @field matches { line: integer, count: integer }[] The first `limit` (default 1000) matching lines
+
+--- @class kubectl.LogRecord
+--- @field text string Formatted line including prefix
+--- @field prefix string Pod/container prefix of text
+--- @field spans integer[][] Include match byte ranges in text
+--- @field format? "json"|"logfmt" Set when the line was parsed
+--- @field timestamp? string
+--- @field level? "trace"|"debug"|"info"|"warn"|"error"|"fatal"
+--- @field msg? string
+--- @field fields? table<string, string> Requested keys present in the line
 
 --- @class kubectl.DescribeSession
 --- @field open fun(self: kubectl.DescribeSession): boolean
@@ -27,6 +48,46 @@
 --- @field read_chunk fun(self: kubectl.NodeShellSession): string?
 --- @field write fun(self: kubectl.NodeShellSession, data: string)
 
+--- @class kubectl.ForwardedPort
+--- @field local_port integer Bound port, also when 0 (any free port) was requested
+--- @field remote_port integer
+--- @field name? string Container or service port name the remote port was resolved from
+
+--- @class kubectl.PortRequest
+--- @field local_port? integer 0 or nil picks a free port
+--- @field remote_port integer|string Port number or container/service port name
+
+--- @class kubectl.PortForward
+--- @field id integer
+--- @field type "pod"|"service"
+--- @field context string Context the forward was started in
+--- @field name string
+--- @field namespace string
+--- @field host string
+--- @field ports kubectl.ForwardedPort[]
+--- @field status "active"|"reconnecting"|"failed"
+--- @field pod? string Pod connections currently go to; pods of a workload and services are re-resolved
+--- @field error? string Last connection or resolve error
+--- @field round_robin boolean Connections rotate over the service's ready endpoints
+--- @field active_connections integer
+--- @field total_connections integer
+--- @field bytes_in integer Bytes sent by local clients
+--- @field bytes_out integer Bytes sent back to local clients
+--- @field last_activity? string RFC 3339 time of the last connection or transfer
+
+--- @class kubectl.PortForwardConnection
+--- @field peer string Local client address
+--- @field remote_port integer
+--- @field pod? string
+--- @field started string
+--- @field duration_ms integer
+--- @field bytes_in integer
+--- @field bytes_out integer
+--- @field error? string
+
+--- @class kubectl.PortForwardDetail: kubectl.PortForward
+--- @field connections kubectl.PortForwardConnection[] The last 50 connections, newest first
+
 --- @class kubectl.ExecConfig
 --- @field namespace string Namespace of the pod
 --- @field pod string Pod name
@@ -58,9 +119,14 @@
 --- @field exec fun(config: kubectl.ExecConfig): kubectl.Session
 --- @field debug fun(config: kubectl.DebugConfig): kubectl.Session
 --- @field node_shell fun(config: kubectl.NodeShellConfig): kubectl.NodeShellSession
---- @field portforward_start fun(kind: string, name: string, namespace: string, local_port: number, remote_port: number)
---- @field portforward_list fun()
+--- @field portforward_start fun(kind: string, name: string, ns: string, host: string, ports: kubectl.PortRequest[], opts?: { round_robin?: boolean }): integer, kubectl.ForwardedPort[]
+--- @field portforward_start_all fun(json: string): string
+--- @field portforward_list fun(): table<integer, kubectl.PortForward>
+--- @field portforward_detail fun(id: integer): kubectl.PortForwardDetail
 --- @field portforward_stop fun(id: number)
+--- @field log_export_start fun(config: kubectl.LogConfig, options: kubectl.LogExportOptions): integer
+--- @field log_export_list fun(): table<integer, kubectl.LogExport>
+--- @field log_export_stop fun(id: integer)
 --- @field daemonset_set_images fun(name: string, ns: string, image_spec: {} )
 --- @field deployment_set_images fun(name: string, ns: string, image_spec: {} )
 --- @field statefulset_set_images fun(name: string, ns: string, image_spec: {} )
@@ -72,6 +138,11 @@
 --- @field uncordon_node fun(name: string)
 --- @field cordon_node fun(name: string)
 --- @field get_config fun()
+--- @field configure_store fun(idle_ttl: number, max_objects: number)
+--- @field store_stats fun(): kubectl.StoreStat[]
+--- @field reflector_status fun(json: string): kubectl.ReflectorStatus?
+--- @field get_table_delta fun(json: string): string JSON encoded kubectl.TableDiff
+--- @field drop_table_session fun(session: string)
 --- @field setup_queue fun()
 --- @field pop_queue fun()
 --- @field emit fun(key, payload)
@@ -87,6 +158,41 @@
 --- @field export_lineage_subgraph_dot fun(tree_id: string, resource_key: string): string
 --- @field export_lineage_subgraph_mermaid fun(tree_id: string, resource_key: string): string
 
+--- @class kubectl.StoreStat
+--- @field context string
+--- @field kind string
+--- @field namespace string?
+--- @field label_selector string?
+--- @field field_selector string?
+--- @field metadata_only boolean
+--- @field objects integer
+--- @field state kubectl.SyncState
+--- @field last_error string?
+--- @field idle_secs integer
+
+--- @alias kubectl.SyncState "initializing"|"synced"|"backing_off"|"forbidden"
+
+--- @class kubectl.ReflectorStatus
+--- @field state kubectl.SyncState
+--- @field last_error string?
+
+--- @class kubectl.TableWindow
+--- Returned by get_table_async instead of a row list when offset, limit or locate is set
+--- @field total integer Rows after filtering
+--- @field offset integer Rows skipped before `rows`
+--- @field rows table[]
+--- @field index integer? 1-based position of the `locate` row in the full table
+
+--- @class kubectl.TableDiff
+--- @field full boolean All rows of the window are in `added`
+--- @field added table[] Rows with a `key` field ("namespace/name")
+--- @field changed table[]
+--- @field removed string[]
+--- @field order string[]? Keys of the window in display order, set when it changed
+--- @field total integer Rows after filtering, before windowing
+--- @field offset integer Rows before the window
+--- @field index integer? 1-based position of the `locate` row
+
 --- @class kubectl.ToggleJsonResult
 --- @field json string
 --- @field start_idx integer
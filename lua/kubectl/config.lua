@@ -30,6 +30,7 @@ local function validate_config(opts)
     statusline = { opts.statusline, "table", true },
     obj_fresh = { opts.obj_fresh, "number", true },
     api_resources_cache_ttl = { opts.api_resources_cache_ttl, "number", true },
+    store = { opts.store, "table", true },
   })
 
   return ok, err
@@ -42,12 +43,13 @@ end
 ---@alias HeadersConfig { enabled: boolean, blend: integer, hints: boolean, context: boolean, heartbeat: boolean, skew: SkewConfig }
 ---@alias LineageConfig { enabled: boolean }
 ---@alias LspConfig { enabled: boolean }
----@alias LogsConfig { prefix: boolean, timestamps: boolean, since: string }
+---@alias LogsConfig { prefix: boolean, timestamps: boolean, since: string, structured: boolean, fields: string[], cronjob_runs: integer }
 ---@alias AliasConfig { apply_on_select_from_history: boolean, max_history: number }
 ---@alias FilterConfig { apply_on_select_from_history: boolean, max_history: number }
 ---@alias FilterLabelConfig { max_history: number }
 ---@alias FloatSizeConfig { width: number, height: number, col: number, row: number }
 ---@alias StatuslineConfig { enabled: boolean }
+---@alias StoreConfig { idle_ttl: number, max_objects: number, metadata_only: string[] }
 
 ---@class KubectlOptions
 ---@field log_level number
@@ -67,6 +69,7 @@ end
 ---@field statusline StatuslineConfig
 ---@field obj_fresh number
 ---@field api_resources_cache_ttl number
+---@field store StoreConfig
 
 ---@type KubectlOptions
 local defaults = {
@@ -104,6 +107,9 @@ local defaults = {
     prefix = true,
     timestamps = true,
     since = "5m",
+    structured = false,
+    fields = {},
+    cronjob_runs = 3,
   },
   alias = {
     apply_on_select_from_history = true,
@@ -131,6 +137,11 @@ local defaults = {
   },
   obj_fresh = 5, -- highlight if age is less than minutes
   api_resources_cache_ttl = 60 * 60 * 3,
+  store = {
+    idle_ttl = 0, -- seconds a reflector may go unread before it is stopped, 0 disables
+    max_objects = 0, -- cap on cached objects across all reflectors, 0 is unlimited
+    metadata_only = {}, -- kinds watched without their payload, e.g. { "Secret", "ConfigMap" }
+  },
 }
 
 ---@type KubectlOptions
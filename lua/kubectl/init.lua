@@ -3,6 +3,7 @@ local splash = require("kubectl.splash")
 local M = {
   is_open = false,
   did_setup = false,
+  profiles_restored = false,
 }
 
 --- Initialize UI components (called after client is ready)
@@ -65,6 +66,14 @@ function M.init(callback)
       splash.status("Client initialized ✔ ")
       M.is_open = true
       init_ui()
+      -- Context switches restore their own profiles; reopening must not start them again
+      if not M.profiles_restored then
+        M.profiles_restored = true
+        vim.schedule(function()
+          local state = require("kubectl.state")
+          require("kubectl.views.portforward.profiles").restore(state.context["current-context"])
+        end)
+      end
       if callback then
         vim.schedule(function()
           callback(true)
@@ -4,6 +4,7 @@ local M = {}
 
 --- kubectl subcommands for completion
 local subcommands = {
+  "aggregate",
   "annotate",
   "api-resources",
   "api-versions",
@@ -35,6 +36,7 @@ local subcommands = {
   "logs",
   "options",
   "patch",
+  "pf-profile",
   "port-forward",
   "proxy",
   "replace",
@@ -219,6 +221,40 @@ function M.execute(args)
     return
   end
 
+  -- Special case: "aggregate <context>... | off" merges tables from several contexts
+  if cmd == "aggregate" then
+    local state = require("kubectl.state")
+    local contexts = vim.list_slice(args, 2)
+    if #contexts == 0 or contexts[1] == "off" then
+      state.aggregate_contexts = {}
+      vim.notify("Showing the current context only")
+    else
+      state.aggregate_contexts = contexts
+      vim.notify("Merging tables from " .. table.concat(contexts, ", "))
+    end
+    local view = state.history[#state.history]
+    if view then
+      require("kubectl.views").resource_or_fallback(view)
+    end
+    return
+  end
+
+  -- Special case: "pf-profile <action> [name]" manages saved port forward profiles
+  if cmd == "pf-profile" then
+    local profiles = require("kubectl.views.portforward.profiles")
+    local profile_args = vim.list_slice(args, 2)
+    if profile_args[1] == "start" then
+      require("kubectl").init(function(ok)
+        if ok then
+          profiles.execute(profile_args)
+        end
+      end)
+    else
+      profiles.execute(profile_args)
+    end
+    return
+  end
+
   -- Special case: "apply" shows diff and confirms before applying
   if cmd == "apply" then
     local tmpfile
@@ -364,6 +400,34 @@ function M.complete(_, cmdline)
     end
   end
 
+  if cmd == "aggregate" then
+    -- "Kubectl aggregate <TAB>" -> context names, any number of them
+    local contexts = vim.list_extend({ "off" }, require("kubectl.resources.contexts").list_contexts())
+    if trailing_space then
+      return contexts
+    end
+    return filter_completions(contexts, parts[#parts])
+  end
+
+  if cmd == "pf-profile" then
+    local profiles = require("kubectl.views.portforward.profiles")
+    -- "Kubectl pf-profile <TAB>" -> actions
+    if #parts == 2 and trailing_space then
+      return profiles.actions
+    end
+    if #parts == 3 and not trailing_space then
+      return filter_completions(profiles.actions, parts[3])
+    end
+    -- "Kubectl pf-profile start <TAB>" -> saved profile names
+    if #parts == 3 and trailing_space then
+      return profiles.names()
+    end
+    if #parts == 4 and not trailing_space then
+      return filter_completions(profiles.names(), parts[4])
+    end
+    return {}
+  end
+
   if cmd == "top" then
     -- "Kubectl top <TAB>" -> pods/nodes
     if #parts == 2 and trailing_space then
@@ -61,13 +61,13 @@ end
 ---@param builder table
 ---@return string|nil name
 ---@return string|nil namespace
+---@return string|nil context Set in tables merged from several contexts
 local function get_selection(builder)
   if not builder or not builder.definition then
     return nil, nil
   end
 
-  local def = builder.definition
-  local name_col, ns_col = tables.getColumnIndices(builder.resource, def.headers or {})
+  local name_col, ns_col, context_col = tables.getColumnIndices(builder.resource, builder.headers())
 
   if not name_col then
     return nil, nil
@@ -85,10 +85,12 @@ local function get_selection(builder)
     return nil, nil
   end
 
+  local context = context_col and tables.getCurrentSelection(context_col) or nil
   if ns_col then
-    return tables.getCurrentSelection(name_col, ns_col)
+    local name, ns = tables.getCurrentSelection(name_col, ns_col)
+    return name, ns, context
   else
-    return tables.getCurrentSelection(name_col), nil
+    return tables.getCurrentSelection(name_col), nil, context
   end
 end
 
@@ -111,7 +113,7 @@ function M.get_hover(_params, callback)
     return
   end
 
-  local name, ns = get_selection(builder)
+  local name, ns, context = get_selection(builder)
   if not name then
     callback(nil, nil)
     return
@@ -135,6 +137,7 @@ function M.get_hover(_params, callback)
     gvk = gvk,
     namespace = ns,
     name = name,
+    context = context,
   }, function(content)
     vim.schedule(function()
       -- Check if this request is stale (newer request was made)
@@ -77,15 +77,15 @@ function M.get_mappings()
         local selections = state.getSelections(original_bufnr)
 
         if vim.tbl_count(selections) == 0 then
-          local name, ns = view.getCurrentSelection()
+          local name, ns, context = view.getCurrentSelection()
           if name then
-            selections = { { name = name, namespace = ns } }
+            selections = { { name = name, namespace = ns, context = context } }
           end
         end
 
         local data = {}
         for _, value in ipairs(selections) do
-          table.insert(data, { name = value.name, namespace = value.namespace })
+          table.insert(data, { name = value.name, namespace = value.namespace, context = value.context })
         end
         local builder = manager.get_or_create("delete_view")
 
@@ -101,10 +101,13 @@ function M.get_mappings()
         for _, value in ipairs(selections) do
           local ns_prefix = value.namespace and (value.namespace .. ": ") or ""
           local display = ns_prefix .. value.name
+          if value.context then
+            display = value.context .. " | " .. display
+          end
           table.insert(action_data, {
             text = display,
             value = "» " .. display,
-            cmd = { name = value.name, namespace = value.namespace },
+            cmd = { name = value.name, namespace = value.namespace, context = value.context },
             type = "positional",
             hl = hl.symbols.pending,
           })
@@ -115,10 +118,12 @@ function M.get_mappings()
           for _, value in ipairs(args) do
             local ns = value.cmd.namespace
             local name = value.cmd.name
+            local context = value.cmd.context
 
             local resource_id = ns and (ns .. "/" .. name) or name
             vim.notify("Deleting " .. gvk.k .. ": " .. resource_id, vim.log.levels.INFO)
-            commands.run_async("delete_async", { gvk = gvk, namespace = ns, name = name }, function(_, err)
+            local delete_args = { gvk = gvk, namespace = ns, name = name, context = context }
+            commands.run_async("delete_async", delete_args, function(_, err)
               vim.schedule(function()
                 if not err then
                   vim.notify(gvk.k .. " deleted: " .. name, vim.log.levels.INFO)
@@ -140,10 +145,10 @@ function M.get_mappings()
         if not view_ok then
           view = require("kubectl.resources.fallback")
         end
-        local name, ns = view.getCurrentSelection()
+        local name, ns, context = view.getCurrentSelection()
 
         if name then
-          view.Yaml(name, ns)
+          view.Yaml(name, ns, context)
         end
       end,
     },
@@ -157,9 +162,9 @@ function M.get_mappings()
         if not view_ok then
           view = require("kubectl.resources.fallback")
         end
-        local name, ns = view.getCurrentSelection()
+        local name, ns, context = view.getCurrentSelection()
         if name then
-          view.Desc(name, ns)
+          view.Desc(name, ns, nil, context)
         end
       end,
     },
@@ -230,7 +235,7 @@ function M.get_mappings()
           view = require("kubectl.resources.fallback")
         end
 
-        local name, ns = view.getCurrentSelection()
+        local name, ns, context = view.getCurrentSelection()
 
         if not name then
           vim.notify("Not a valid selection to edit", vim.log.levels.INFO)
@@ -256,6 +261,7 @@ function M.get_mappings()
           namespace = def.ns,
           output = "Yaml",
           cached = false,
+          context = context,
         }, function(data)
           vim.schedule(function()
             local tmpfilename = string.format("%s-%s-%s.yaml", vim.fn.tempname(), name, ns)
@@ -274,7 +280,8 @@ function M.get_mappings()
               group = group,
               callback = function()
                 commands.run_async("edit_async", {
-                  tmpfilename,
+                  path = tmpfilename,
+                  context = context,
                 }, function(result, err)
                   vim.schedule(function()
                     if err then
@@ -511,10 +518,10 @@ function M.get_mappings()
 
         local bufnr = vim.api.nvim_get_current_buf()
         local selections = state.get_buffer_selections(bufnr)
-        local name, ns = current_view.getCurrentSelection()
+        local name, ns, context = current_view.getCurrentSelection()
 
         for i, selection in ipairs(selections) do
-          if selection.name == name and (ns and selection.namespace == ns or true) then
+          if selection.name == name and (ns and selection.namespace == ns or true) and selection.context == context then
             table.remove(selections, i)
             state.set_buffer_selections(bufnr, selections)
             vim.api.nvim_feedkeys("j", "n", true)
@@ -524,7 +531,7 @@ function M.get_mappings()
         end
 
         if name then
-          table.insert(selections, { name = name, namespace = ns })
+          table.insert(selections, { name = name, namespace = ns, context = context })
           state.set_buffer_selections(bufnr, selections)
           vim.api.nvim_feedkeys("j", "n", true)
           current_view.Draw()
@@ -703,6 +710,22 @@ function M.register()
   end
 end
 
+--- View actions run against the current context, so they are refused in tables merged
+--- from several contexts; describe, yaml, edit and delete follow the row's context instead
+---@param callback function
+---@return function
+local function current_context_only(callback)
+  return function(...)
+    local _, buf_name = pcall(vim.api.nvim_buf_get_var, 0, "buf_name")
+    local builder = type(buf_name) == "string" and manager.get(buf_name) or nil
+    if builder and builder.aggregated then
+      vim.notify("Not available in aggregated views, see :Kubectl aggregate off", vim.log.levels.WARN)
+      return
+    end
+    return callback(...)
+  end
+end
+
 --- Apply all mappings for a buffer
 ---@param bufnr number
 ---@param view_name string
@@ -715,7 +738,9 @@ local function apply_mappings(bufnr, view_name)
   local globals = M.get_mappings()
   local locals = {}
   if ok and view_mappings.overrides then
-    locals = view_mappings.overrides
+    for lhs, def in pairs(view_mappings.overrides) do
+      locals[lhs] = vim.tbl_extend("force", def, { callback = def.callback and current_context_only(def.callback) })
+    end
   end
 
   local all_mappings = vim.tbl_deep_extend("force", globals, locals)
@@ -5,6 +5,59 @@ local tables = require("kubectl.utils.tables")
 
 local M = {}
 
+--- Screens of rows an incremental table keeps in its buffer around the cursor
+local WINDOW_SCREENS = 3
+
+--- Rows of an incremental table keep the AGE they were built with; recompute it from the
+--- creation time kept in `sort_by`
+---@param rows table[]
+local function refresh_ages(rows)
+  local time = require("kubectl.utils.time")
+  local hl = require("kubectl.actions.highlight")
+  local now = time.currentTime()
+  for _, row in ipairs(rows) do
+    local age = row.age
+    if type(age) == "table" and age.sort_by then
+      local value, fresh = time.diff_str(now, age.sort_by)
+      age.value = value
+      age.symbol = fresh and hl.symbols.success or nil
+    end
+  end
+end
+
+--- Start the reflector for `gvk` in the current context, or in every aggregated context.
+--- Kinds listed in `store.metadata_only` are watched without their payload.
+--- `on_done` runs once all of them are synced, with the errors of those that failed.
+---@param gvk table
+---@param ns string|nil
+---@param on_done fun(errors: string[])
+function M.start_reflectors(gvk, ns, on_done)
+  local config = require("kubectl.config")
+  local metadata_only = vim.tbl_contains(config.options.store.metadata_only or {}, gvk.k)
+  local contexts = state.aggregate_contexts
+  if #contexts == 0 then
+    local args = { gvk = gvk, namespace = ns, metadata_only = metadata_only }
+    commands.run_async("start_reflector_async", args, function(_, err)
+      on_done(err and { err } or {})
+    end)
+    return
+  end
+
+  local pending, errors = #contexts, {}
+  for _, context in ipairs(contexts) do
+    local args = { gvk = gvk, namespace = ns, context = context, metadata_only = metadata_only }
+    commands.run_async("start_reflector_async", args, function(_, err)
+      if err then
+        table.insert(errors, context .. ": " .. err)
+      end
+      pending = pending - 1
+      if pending == 0 then
+        on_done(errors)
+      end
+    end)
+  end
+end
+
 --- Create a new factory for the given `resource`.
 ---@param resource string  -- e.g., "pods", "deployments"
 ---@return table builder   -- the new builder object
@@ -24,6 +77,11 @@ function M.new(resource)
   builder.header = { data = nil, marks = nil }
   builder.win_nr = nil
   builder.buf_nr = nil
+  -- Slice of an incremental table held in the buffer; `cursor` is the 0-based row to put
+  -- the cursor on once a moved window is drawn
+  builder.window = { offset = 0, total = nil, cursor = nil }
+  ---@type kubectl.ReflectorStatus?
+  builder.reflector_status = nil
 
   ---------------------------------------------------------------------------
   -- LOW-LEVEL UTILITY METHODS
@@ -136,15 +194,24 @@ function M.new(resource)
   -- PRETTY PRINT & DIVIDER
   ---------------------------------------------------------------------------
 
-  function builder.prettyPrint(win_nr)
-    local sort_info = state.sortby[builder.resource]
-    local original_headers = {}
+  --- Headers the table is drawn with; aggregated tables lead with the context each row came from
+  ---@return string[]
+  function builder.headers()
+    local headers = {}
     if builder.definition and builder.definition.headers then
-      original_headers = builder.definition.headers
+      headers = builder.definition.headers
     end
+    if builder.aggregated then
+      headers = vim.list_extend({ "CONTEXT" }, headers)
+    end
+    return headers
+  end
+
+  function builder.prettyPrint(win_nr)
+    local sort_info = state.sortby[builder.resource]
 
     -- Use centralized function for column ordering and visibility
-    local visible_headers = tables.getVisibleHeaders(builder.resource, original_headers)
+    local visible_headers = tables.getVisibleHeaders(builder.resource, builder.headers())
 
     builder.prettyData, builder.extmarks =
       tables.pretty_print(builder.processedData, visible_headers, sort_info, win_nr)
@@ -165,7 +232,9 @@ function M.new(resource)
 
   function builder.addDivider(include_filter)
     local count = ""
-    if builder.prettyData then
+    if builder.delta_rows and builder.window.total then
+      count = tostring(builder.window.total)
+    elseif builder.prettyData then
       count = tostring(#builder.prettyData - 1)
     elseif builder.data then
       count = tostring(#builder.data - 1)
@@ -189,10 +258,20 @@ function M.new(resource)
       filter_str = filter_str .. state.filter_key
     end
 
+    local status = ""
+    local reflector = builder.reflector_status
+    if reflector and (reflector.state == "forbidden" or reflector.state == "backing_off") then
+      status = reflector.state:gsub("_", " ")
+      if type(reflector.last_error) == "string" then
+        status = status .. ": " .. reflector.last_error
+      end
+    end
+
     builder.header.divider_winbar = tables.generateDividerWinbar({
       resource = builder.resource,
       count = count,
       filter = filter_str,
+      status = status,
     }, builder.win_nr)
 
     return builder
@@ -262,17 +341,59 @@ function M.new(resource)
     builder.buf_nr, builder.win_nr = buffers.buffer(definition.ft, builder.resource)
     state.addToHistory(builder.resource)
 
+    local group = vim.api.nvim_create_augroup("kubectl_table_" .. builder.buf_nr, { clear = true })
+    vim.api.nvim_create_autocmd("CursorMoved", {
+      group = group,
+      buffer = builder.buf_nr,
+      callback = function()
+        builder.scrollWindow()
+      end,
+    })
+    vim.api.nvim_create_autocmd("BufWipeout", {
+      group = group,
+      buffer = builder.buf_nr,
+      once = true,
+      callback = function(ev)
+        require("kubectl.client").drop_table_session(resource .. ":" .. ev.buf)
+      end,
+    })
+
     local ns = nil
     if definition.namespaced then
       if state.ns and state.ns ~= "All" then
         ns = state.ns
       end
     end
-    commands.run_async("start_reflector_async", { gvk = definition.gvk, namespace = ns }, function(_, err)
-      if err then
+
+    -- Show in the divider why the watch behind the table is not delivering
+    builder.reflector_status = nil
+    require("kubectl.event_queue").register("reflector_status", builder.buf_nr, function(payload)
+      local ok, ev = pcall(vim.json.decode, payload, { luanil = { object = true } })
+      if not ok or #state.aggregate_contexts > 0 or ev.kind ~= definition.gvk.k then
+        return
+      end
+      if ev.context ~= state.context["current-context"] or (ev.namespace and ev.namespace ~= ns) then
         return
       end
+      builder.reflector_status = { state = ev.state, last_error = ev.last_error }
+      builder.draw()
+    end)
+
+    M.start_reflectors(definition.gvk, ns, function(errors)
+      if #errors > 0 then
+        vim.schedule(function()
+          local msg = table.concat(errors, "\n")
+          vim.notify("kubectl: watch for " .. definition.gvk.k .. " failed: " .. msg, vim.log.levels.WARN)
+        end)
+      end
       vim.schedule(function()
+        -- A forbidden or failing watch keeps its reflector, so its state can be shown
+        if #state.aggregate_contexts == 0 then
+          builder.reflector_status = require("kubectl.client").reflector_status(definition.gvk, ns)
+        end
+        if #errors == math.max(#state.aggregate_contexts, 1) and not builder.reflector_status then
+          return
+        end
         -- Check buffer validity before drawing (buffer may have been deleted)
         if builder.buf_nr and not vim.api.nvim_buf_is_valid(builder.buf_nr) then
           return
@@ -285,6 +406,56 @@ function M.new(resource)
     return builder
   end
 
+  --- Apply a kubectl.TableDiff to the rows kept from the previous draw
+  ---@param diff kubectl.TableDiff
+  function builder.applyDiff(diff)
+    if diff.full or not builder.delta_rows then
+      builder.delta_rows, builder.delta_order = {}, {}
+    end
+    for _, row in ipairs(diff.added) do
+      builder.delta_rows[row.key] = row
+    end
+    for _, row in ipairs(diff.changed) do
+      builder.delta_rows[row.key] = row
+    end
+    for _, key in ipairs(diff.removed) do
+      builder.delta_rows[key] = nil
+    end
+    builder.delta_order = diff.order or builder.delta_order
+
+    local rows = {}
+    for _, key in ipairs(builder.delta_order) do
+      table.insert(rows, builder.delta_rows[key])
+    end
+    builder.data = rows
+    builder.processedData = rows
+    return builder
+  end
+
+  --- Move the window of an incremental table once the cursor nears the first or last
+  --- row it holds, keeping a screen of rows above the cursor
+  function builder.scrollWindow()
+    local total = builder.window.total
+    if not builder.delta_rows or not total or builder.window.cursor then
+      return
+    end
+    local win = vim.api.nvim_get_current_win()
+    local height = vim.api.nvim_win_get_height(win)
+    -- The first line holds the column headers
+    local row = vim.api.nvim_win_get_cursor(win)[1] - 1
+    local offset, shown = builder.window.offset, #builder.delta_order
+    local near_top = offset > 0 and row <= height
+    local near_bottom = offset + shown < total and row > shown - height
+    if not near_top and not near_bottom then
+      return
+    end
+
+    local cursor = offset + math.max(row - 1, 0)
+    builder.window.offset = math.max(cursor - height, 0)
+    builder.window.cursor = cursor
+    builder.draw()
+  end
+
   --- Update winbar for all windows showing this buffer
   local function update_winbars(windows, winbar_content)
     for _, win_id in ipairs(windows) do
@@ -321,19 +492,64 @@ function M.new(resource)
       filter = filter,
       filter_label = filter_label,
       filter_key = filter_key,
+      contexts = #state.aggregate_contexts > 0 and state.aggregate_contexts or nil,
     }
 
-    commands.run_async("get_table_async", args, function(data, err)
-      if err then
-        return
+    -- Single-context tables only fetch the rows of the window around the cursor that
+    -- changed since the last draw
+    local method = "get_table_async"
+    if not args.contexts then
+      -- One refresh per session at a time; a draw asked for meanwhile runs once it is done
+      if builder.delta_in_flight then
+        builder.delta_pending = true
+        return builder
+      end
+      builder.delta_in_flight = true
+      local windows = buffers.get_windows_by_name(resource)
+      local height = #windows > 0 and vim.api.nvim_win_get_height(windows[1]) or vim.o.lines
+      method = "get_table_delta"
+      args.session = resource .. ":" .. builder.buf_nr
+      args.full = builder.delta_rows == nil
+      args.offset = builder.window.offset
+      args.limit = height * WINDOW_SCREENS
+    end
+
+    commands.run_async(method, args, function(data, err)
+      if method == "get_table_delta" then
+        builder.delta_in_flight = nil
+        if builder.delta_pending then
+          builder.delta_pending = nil
+          vim.schedule(function()
+            builder.draw()
+          end)
+        end
       end
-      if not data then
+      if err or not data then
+        builder.window.cursor = nil
         return
       end
 
       builder.data = data
       builder.decodeJson()
-      builder.processedData = builder.data
+      if method == "get_table_delta" then
+        local diff = builder.data
+        -- Filtered down past the window: start over from the top
+        if diff.offset > 0 and diff.offset >= diff.total then
+          builder.window.offset, builder.window.cursor = 0, nil
+          builder.delta_rows = nil
+          vim.schedule(function()
+            builder.draw(cancellationToken)
+          end)
+          return
+        end
+        builder.window.offset, builder.window.total = diff.offset, diff.total
+        builder.applyDiff(diff)
+        refresh_ages(builder.processedData)
+      else
+        builder.delta_rows = nil
+        builder.processedData = builder.data
+      end
+      builder.aggregated = args.contexts ~= nil
 
       vim.schedule(function()
         if definition.processRow then
@@ -360,6 +576,13 @@ function M.new(resource)
         -- Set buffer content once (all windows see the same buffer)
         builder.displayContent(primary_win, cancellationToken)
 
+        local cursor = builder.window.cursor
+        if cursor then
+          builder.window.cursor = nil
+          local line = math.min(cursor - builder.window.offset + 2, #builder.prettyData)
+          pcall(vim.api.nvim_win_set_cursor, primary_win, { math.max(line, 2), 0 })
+        end
+
         -- Update diagnostics immediately after content (same render frame)
         local diagnostics = require("kubectl.lsp.diagnostics")
         diagnostics.set_diagnostics(builder.buf_nr, resource)
@@ -39,19 +39,21 @@ function BaseResource.extend(definition)
   ---@param name string Resource name
   ---@param ns string|nil Namespace (nil for cluster-scoped)
   ---@param _ boolean|nil Whether to reload (deprecated, kept for API compatibility)
-  function M.Desc(name, ns, _)
+  ---@param context string|nil Context of the row (aggregated tables), defaults to the current one
+  function M.Desc(name, ns, _, context)
     local gvk = { k = M.definition.resource, g = M.definition.gvk.g, v = M.definition.gvk.v }
     local namespace = nil
     if M.definition.namespaced then
       namespace = ns
     end
-    describe_session.view(M.definition.resource, name, namespace, gvk)
+    describe_session.view(M.definition.resource, name, namespace, gvk, context)
   end
 
   --- View YAML for a specific resource
   ---@param name string Resource name
   ---@param ns string|nil Namespace (nil for cluster-scoped)
-  function M.Yaml(name, ns)
+  ---@param context string|nil Context of the row (aggregated tables), defaults to the current one
+  function M.Yaml(name, ns, context)
     local display_ns = ns and (" | " .. ns) or ""
     local title = M.definition.resource .. " | " .. name .. display_ns
 
@@ -74,23 +76,30 @@ function BaseResource.extend(definition)
         namespace = M.definition.namespaced and ns or nil,
         name = name,
         output = "yaml",
+        context = context,
       },
       recreate_func = M.Yaml,
-      recreate_args = { name, ns },
+      recreate_args = { name, ns, context },
     })
   end
 
   --- Get current selection from buffer
-  ---@return string|nil ... Returns name and optionally namespace
+  ---@return string|nil name
+  ---@return string|nil namespace
+  ---@return string|nil context Set in tables merged from several contexts
   function M.getCurrentSelection()
-    local name_col, ns_col = tables.getColumnIndices(M.definition.resource, M.definition.headers or {})
+    local builder = manager.get(M.definition.resource)
+    local headers = builder and builder.headers() or M.definition.headers or {}
+    local name_col, ns_col, context_col = tables.getColumnIndices(M.definition.resource, headers)
     if not name_col then
       return nil
     end
+    local context = context_col and tables.getCurrentSelection(context_col) or nil
     if ns_col then
-      return tables.getCurrentSelection(name_col, ns_col)
+      local name, ns = tables.getCurrentSelection(name_col, ns_col)
+      return name, ns, context
     else
-      return tables.getCurrentSelection(name_col)
+      return tables.getCurrentSelection(name_col), nil, context
     end
   end
 
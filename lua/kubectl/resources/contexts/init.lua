@@ -125,6 +125,7 @@ function M.change_context(cmd)
 
   vim.schedule(function()
     local state = require("kubectl.state")
+    local changed = state.context["current-context"] ~= cmd
     state.context["current-context"] = cmd
 
     local cache = require("kubectl.cache")
@@ -143,6 +144,9 @@ function M.change_context(cmd)
           cache.LoadFallbackData()
           local lineage = require("kubectl.views.lineage")
           lineage.loaded = false
+          if changed then
+            require("kubectl.views.portforward.profiles").restore(cmd)
+          end
           vim.api.nvim_exec_autocmds("User", {
             pattern = "K8sContextChanged",
             data = { context = cmd },
@@ -25,9 +25,9 @@ local M = BaseResource.extend({
 M.selection = {}
 
 -- Override Desc to use plural for the gvk.k
-function M.Desc(name, _, _)
+function M.Desc(name, _, _, context)
   local gvk = { k = M.definition.plural, g = M.definition.gvk.g, v = M.definition.gvk.v }
-  describe_session.view(M.definition.resource, name, nil, gvk)
+  describe_session.view(M.definition.resource, name, nil, gvk, context)
 end
 
 return M
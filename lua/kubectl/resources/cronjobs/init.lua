@@ -17,6 +17,7 @@ local M = BaseResource.extend({
   },
   hints = {
     { key = "<Plug>(kubectl.create_job)", desc = "create", long_desc = "Create job from cronjob" },
+    { key = "<Plug>(kubectl.logs)", desc = "logs", long_desc = "Shows logs of the most recent runs" },
     { key = "<Plug>(kubectl.select)", desc = "pods", long_desc = "Opens pods view" },
     { key = "<Plug>(kubectl.suspend_cronjob)", desc = "suspend", long_desc = "Suspend/Unsuspend cronjob" },
   },
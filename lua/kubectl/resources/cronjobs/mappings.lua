@@ -9,6 +9,14 @@ local M = {}
 local err_msg = "Failed to extract cronjob name or namespace."
 
 M.overrides = {
+  ["<Plug>(kubectl.logs)"] = {
+    noremap = true,
+    silent = true,
+    desc = "View logs of recent runs",
+    callback = mapping_helpers.safe_callback(cronjob_view, function(name, ns)
+      require("kubectl.resources.pods").JobLogs("CronJob", name, ns)
+    end),
+  },
   ["<Plug>(kubectl.create_job)"] = {
     noremap = true,
     silent = true,
@@ -87,6 +95,7 @@ M.overrides = {
 
 M.register = function()
   mappings.map_if_plug_not_set("n", "gc", "<Plug>(kubectl.create_job)")
+  mappings.map_if_plug_not_set("n", "gl", "<Plug>(kubectl.logs)")
   mappings.map_if_plug_not_set("n", "gss", "<Plug>(kubectl.suspend_cronjob)")
 end
 
@@ -48,7 +48,7 @@ function M.View(cancellationToken, kind)
       ns = state.ns
     end
   end
-  commands.run_async("start_reflector_async", { gvk = M.definition.gvk, namespace = ns }, function()
+  require("kubectl.resource_factory").start_reflectors(M.definition.gvk, ns, function()
     vim.schedule(function()
       M.Draw(cancellationToken)
       vim.cmd("doautocmd User K8sDataLoaded")
@@ -78,6 +78,7 @@ function M.Draw(cancellationToken)
   local sort_order = state.sortby[builder.definition.resource] and state.sortby[builder.definition.resource].order
     or nil
 
+  local contexts = #state.aggregate_contexts > 0 and state.aggregate_contexts or nil
   commands.run_async("get_fallback_table_async", {
     gvk = builder.definition.gvk,
     namespace = ns,
@@ -86,12 +87,14 @@ function M.Draw(cancellationToken)
     filter = filter,
     filter_label = filter_label,
     filter_key = filter_key,
+    contexts = contexts,
   }, function(result)
     if not result then
       return
     end
     builder.data = result
     builder.decodeJson()
+    builder.aggregated = contexts ~= nil
     builder.processedData = builder.data.rows
     builder.definition.headers = builder.data.headers
     builder.sort()
@@ -108,13 +111,13 @@ function M.Draw(cancellationToken)
   end)
 end
 
-function M.Desc(name, ns, _)
+function M.Desc(name, ns, _, context)
   -- Use plural for the gvk.k as fallback resources need it
   local gvk = { k = M.definition.plural, g = M.definition.gvk.g, v = M.definition.gvk.v }
-  describe_session.view(M.definition.resource, name, ns, gvk)
+  describe_session.view(M.definition.resource, name, ns, gvk, context)
 end
 
-function M.Yaml(name, ns)
+function M.Yaml(name, ns, context)
   local display_ns = ns and (" | " .. ns) or ""
   local title = M.definition.resource .. " | " .. name .. display_ns
 
@@ -137,23 +140,30 @@ function M.Yaml(name, ns)
       namespace = ns,
       name = name,
       output = "yaml",
+      context = context,
     },
     recreate_func = M.Yaml,
-    recreate_args = { name, ns },
+    recreate_args = { name, ns, context },
   })
 end
 
 --- Get current seletion for view
----@return string|nil, string|nil
+---@return string|nil name
+---@return string|nil namespace
+---@return string|nil context Set in tables merged from several contexts
 function M.getCurrentSelection()
-  local name_col, ns_col = tables.getColumnIndices(M.definition.resource, M.definition.headers or {})
+  local builder = manager.get(M.definition.resource)
+  local headers = builder and builder.headers() or M.definition.headers or {}
+  local name_col, ns_col, context_col = tables.getColumnIndices(M.definition.resource, headers)
   if not name_col then
     return nil, nil
   end
+  local context = context_col and tables.getCurrentSelection(context_col) or nil
   if ns_col then
-    return tables.getCurrentSelection(name_col, ns_col)
+    local name, ns = tables.getCurrentSelection(name_col, ns_col)
+    return name, ns, context
   end
-  return tables.getCurrentSelection(name_col), nil
+  return tables.getCurrentSelection(name_col), nil, context
 end
 
 return M
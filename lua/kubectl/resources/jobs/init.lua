@@ -14,6 +14,7 @@ return BaseResource.extend({
     end,
   },
   hints = {
+    { key = "<Plug>(kubectl.logs)", desc = "logs", long_desc = "Shows logs of all pods of the job" },
     { key = "<Plug>(kubectl.select)", desc = "pods", long_desc = "Opens pods view" },
   },
   headers = {
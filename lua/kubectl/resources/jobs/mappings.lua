@@ -1,5 +1,22 @@
+local job_view = require("kubectl.resources.jobs")
+local mapping_helpers = require("kubectl.utils.mapping_helpers")
+local mappings = require("kubectl.mappings")
+
 local M = {}
 
-M.overrides = {}
+M.overrides = {
+  ["<Plug>(kubectl.logs)"] = {
+    noremap = true,
+    silent = true,
+    desc = "View job logs",
+    callback = mapping_helpers.safe_callback(job_view, function(name, ns)
+      require("kubectl.resources.pods").JobLogs("Job", name, ns)
+    end),
+  },
+}
+
+M.register = function()
+  mappings.map_if_plug_not_set("n", "gl", "<Plug>(kubectl.logs)")
+end
 
 return M
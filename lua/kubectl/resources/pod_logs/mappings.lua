@@ -34,6 +34,82 @@ local function toggle_json()
   end
 end
 
+--- Start a background export of the logs shown in this buffer, or stop a running one
+local function export_logs()
+  local ok, pods = pcall(vim.api.nvim_buf_get_var, 0, "kubectl_log_pods")
+  local selector = vim.b.kubectl_log_selector
+  if (not ok or not pods or #pods == 0) and not selector then
+    return vim.notify("No pods to export", vim.log.levels.WARN)
+  end
+
+  local function start()
+    vim.ui.input({ prompt = "Export logs to: ", completion = "file" }, function(path)
+      if not path or path == "" then
+        return
+      end
+      path = vim.fn.expand(path)
+      local opts = log_session.get_options()
+      local export_ok, id = pcall(client.log_export_start, {
+        pods = pods,
+        selector = selector,
+        container = pod_view.selection.container,
+        since = opts.since,
+        prefix = opts.prefix,
+        include = opts.include,
+        exclude = opts.exclude,
+        level = opts.level,
+      }, {
+        path = path,
+        format = path:match("%.jsonl") and "jsonl" or "plain",
+        gzip = path:match("%.gz$") ~= nil,
+      })
+      if export_ok then
+        vim.notify("Exporting logs to " .. path .. " (#" .. id .. ")")
+      else
+        vim.notify("Log export failed: " .. tostring(id), vim.log.levels.ERROR)
+      end
+    end)
+  end
+
+  -- Finished and failed exports stay listed until cleared, so their outcome can be seen
+  local exports = {}
+  for _, export in pairs(client.log_export_list()) do
+    table.insert(exports, export)
+  end
+  if #exports == 0 then
+    return start()
+  end
+  table.sort(exports, function(a, b)
+    return a.id < b.id
+  end)
+
+  table.insert(exports, 1, { new = true })
+  vim.ui.select(exports, {
+    prompt = "Log exports",
+    format_item = function(item)
+      if item.new then
+        return "New export..."
+      end
+      if item.status == "running" then
+        return string.format("Stop #%d %s (%d lines)", item.id, item.path, item.lines)
+      end
+      local outcome = item.error and ("failed: " .. item.error) or item.status
+      return string.format("Clear #%d %s (%d lines, %s)", item.id, item.path, item.lines, outcome)
+    end,
+  }, function(choice)
+    if not choice then
+      return
+    end
+    if choice.new then
+      return start()
+    end
+    client.log_export_stop(choice.id)
+    if choice.status == "running" then
+      vim.notify("Stopped log export #" .. choice.id)
+    end
+  end)
+end
+
 --- Get current options and update session manager
 ---@param key string Option key to toggle
 ---@param value any New value (or nil to toggle boolean)
@@ -79,6 +155,16 @@ M.overrides = {
     end,
   },
 
+  ["<Plug>(kubectl.ordered_logs)"] = {
+    noremap = true,
+    silent = true,
+    desc = "Toggle chronological merge",
+    callback = function()
+      update_option("ordered")
+      pod_view.Logs()
+    end,
+  },
+
   ["<Plug>(kubectl.timestamps)"] = {
     noremap = true,
     silent = true,
@@ -103,6 +189,21 @@ M.overrides = {
       end)
     end,
   },
+  ["<Plug>(kubectl.tail_logs)"] = {
+    noremap = true,
+    silent = true,
+    desc = "Log tail lines",
+    callback = function()
+      local opts = log_session.get_options()
+      vim.ui.input({ prompt = "Tail lines (0 = all)=", default = tostring(opts.tail or 0) }, function(input)
+        local n = tonumber(input)
+        if n then
+          update_option("tail", math.max(math.floor(n), 0))
+        end
+        pod_view.Logs()
+      end)
+    end,
+  },
   ["<Plug>(kubectl.prefix)"] = {
     noremap = true,
     silent = true,
@@ -115,11 +216,52 @@ M.overrides = {
   ["<Plug>(kubectl.select)"] = {
     noremap = true,
     silent = true,
-    desc = "Add divider",
+    desc = "Jump to histogram bucket or add divider",
+    callback = function()
+      if not pod_view.JumpToHistogramBucket(vim.api.nvim_get_current_buf()) then
+        str.divider(0)
+      end
+    end,
+  },
+  ["<Plug>(kubectl.log_search)"] = {
+    noremap = true,
+    silent = true,
+    desc = "Search followed logs",
+    callback = function()
+      local buf = vim.api.nvim_get_current_buf()
+      vim.ui.input({ prompt = "Search logs (regex)=" }, function(input)
+        if input and input ~= "" then
+          log_session.search(buf, input)
+        end
+      end)
+    end,
+  },
+  ["<Plug>(kubectl.crash_logs)"] = {
+    noremap = true,
+    silent = true,
+    desc = "Toggle crash context",
+    callback = function()
+      update_option("crash")
+      pod_view.Logs()
+    end,
+  },
+  ["<Plug>(kubectl.histogram_split)"] = {
+    noremap = true,
+    silent = true,
+    desc = "Cycle histogram split",
     callback = function()
-      str.divider(0)
+      local next_split = { total = "level", level = "pod", pod = "total" }
+      local current = log_session.get_options().histogram_split or "total"
+      update_option("histogram_split", next_split[current] or "total")
+      pod_view.Logs()
     end,
   },
+  ["<Plug>(kubectl.export_logs)"] = {
+    noremap = true,
+    silent = true,
+    desc = "Export logs to file",
+    callback = export_logs,
+  },
   ["<Plug>(kubectl.expand_json)"] = {
     noremap = true,
     silent = true,
@@ -133,10 +275,16 @@ function M.register()
   mappings.map_if_plug_not_set("n", "gw", "<Plug>(kubectl.wrap)")
   mappings.map_if_plug_not_set("n", "gp", "<Plug>(kubectl.prefix)")
   mappings.map_if_plug_not_set("n", "gt", "<Plug>(kubectl.timestamps)")
+  mappings.map_if_plug_not_set("n", "gm", "<Plug>(kubectl.ordered_logs)")
   mappings.map_if_plug_not_set("n", "gh", "<Plug>(kubectl.history)")
+  mappings.map_if_plug_not_set("n", "gT", "<Plug>(kubectl.tail_logs)")
   mappings.map_if_plug_not_set("n", "<CR>", "<Plug>(kubectl.select)")
   mappings.map_if_plug_not_set("n", "gpp", "<Plug>(kubectl.previous_logs)")
   mappings.map_if_plug_not_set("n", "gj", "<Plug>(kubectl.expand_json)")
+  mappings.map_if_plug_not_set("n", "gE", "<Plug>(kubectl.export_logs)")
+  mappings.map_if_plug_not_set("n", "gH", "<Plug>(kubectl.histogram_split)")
+  mappings.map_if_plug_not_set("n", "gc", "<Plug>(kubectl.crash_logs)")
+  mappings.map_if_plug_not_set("n", "g/", "<Plug>(kubectl.log_search)")
 end
 
 return M
@@ -1,4 +1,6 @@
 local BaseResource = require("kubectl.resources.base_resource")
+local config = require("kubectl.config")
+local hl = require("kubectl.actions.highlight")
 local log_session = require("kubectl.views.logs.session")
 local manager = require("kubectl.resource_manager")
 local pf_action = require("kubectl.actions.portforward")
@@ -39,6 +41,115 @@ local M = BaseResource.extend({
 -- Pod-specific state (simple selection tracking)
 M.selection = {}
 
+-- Histogram bucket data of each log buffer, for jumping to a time window
+M.log_histograms = {}
+
+local histogram_hl = {
+  error = hl.symbols.error,
+  warn = hl.symbols.warning,
+  info = hl.symbols.info,
+  other = hl.symbols.gray,
+}
+
+local crash_hl = {
+  container = hl.symbols.header,
+  termination = hl.symbols.error,
+  section = hl.symbols.gray,
+}
+
+--- Show previous logs, current logs and the last termination of each container
+---@param builder table
+---@param pods table[]
+---@param container string?
+---@param selector kubectl.LogSelector?
+---@param opts kubectl.LogSessionOptions
+local function crash_logs(builder, pods, container, selector, opts)
+  local buffers = require("kubectl.actions.buffers")
+  local commands = require("kubectl.actions.commands")
+
+  commands.run_async("crash_logs_async", {
+    pods = pods,
+    selector = selector,
+    container = container,
+    since = not selector and opts.since or nil,
+    tail_lines = (opts.tail or 0) > 0 and opts.tail or nil,
+    timestamps = opts.timestamps,
+    prefix = opts.prefix,
+    include = opts.include,
+    exclude = opts.exclude,
+    level = opts.level,
+    field_filters = opts.field_filters,
+  }, function(result)
+    if not result then
+      return
+    end
+    builder.data = result
+    builder.decodeJson()
+    local payload = builder.data or {}
+    builder.data = payload.lines or {}
+    M.log_histograms[builder.buf_nr] = nil
+    local marks = {}
+    for _, mark in ipairs(payload.marks or {}) do
+      local line = builder.data[mark.row + 1] or ""
+      table.insert(marks, { row = mark.row, start_col = 0, end_col = #line, hl_group = crash_hl[mark.kind] })
+    end
+    vim.schedule(function()
+      buffers.set_content(builder.buf_nr, {
+        content = builder.data,
+        marks = marks,
+        header = { data = {}, marks = {} },
+      })
+    end)
+  end)
+end
+
+--- Highlight the rows of a level histogram
+---@param histogram table?
+---@param lines string[]
+---@return table[] marks
+local function histogram_marks(histogram, lines)
+  local marks = {}
+  if not histogram then
+    return marks
+  end
+  for i, name in ipairs(histogram.series) do
+    if histogram_hl[name] and lines[i] then
+      table.insert(marks, { row = i - 1, start_col = 0, end_col = #lines[i], hl_group = histogram_hl[name] })
+    end
+  end
+  return marks
+end
+
+--- Jump from a histogram bar under the cursor to the first log line in its bucket
+---@param buf integer
+---@return boolean handled False when the cursor is not on a histogram row
+function M.JumpToHistogramBucket(buf)
+  local histogram = M.log_histograms[buf]
+  local row = vim.api.nvim_win_get_cursor(0)[1]
+  if not histogram or row >= histogram.rows then
+    return false
+  end
+
+  local bucket = histogram.buckets[vim.fn.virtcol(".") - histogram.bar_col]
+  if not bucket then
+    return true
+  end
+
+  local counts = {}
+  for i, name in ipairs(histogram.series) do
+    table.insert(counts, name .. "=" .. bucket.counts[i])
+  end
+  local window = bucket.start .. " - " .. bucket["end"]
+  if not bucket.first_line then
+    vim.notify("No logs in " .. window)
+    return true
+  end
+
+  pcall(vim.api.nvim_win_set_cursor, 0, { histogram.rows + bucket.first_line, 0 })
+  vim.notify(window .. ": " .. table.concat(counts, " "))
+  return true
+end
+
 function M.onBeforeDraw(builder)
   local pfs = pf_view.getPFRows(string.lower(M.definition.gvk.k))
   builder.extmarks_extra = {}
@@ -52,6 +163,7 @@ end
 --- Build pods list from selections or single selection
 ---@return table pods List of { name, namespace } entries
 ---@return string display_name Display name for the view
+---@return kubectl.LogSelector? selector Job or CronJob the logs view was opened for
 local function get_pods_for_logs()
   local current_buf = vim.api.nvim_get_current_buf()
   local current_ft = vim.api.nvim_get_option_value("filetype", { buf = current_buf })
@@ -60,6 +172,10 @@ local function get_pods_for_logs()
   if current_ft == "k8s_pod_logs" then
     local ok, pods = pcall(vim.api.nvim_buf_get_var, current_buf, "kubectl_log_pods")
     local ok2, display = pcall(vim.api.nvim_buf_get_var, current_buf, "kubectl_log_display")
+    local selector = vim.b[current_buf].kubectl_log_selector
+    if ok2 and selector then
+      return {}, display, selector
+    end
     if ok and ok2 and pods and #pods > 0 then
       return pods, display
     end
@@ -90,7 +206,8 @@ end
 ---@param pods table[] Array of {name, namespace} tables
 ---@param display_name string Display name for the title
 ---@param container string|nil Container name
-function M.LogsWithPods(pods, display_name, container)
+---@param selector kubectl.LogSelector? Resolve pods from a Job or CronJob instead of `pods`
+function M.LogsWithPods(pods, display_name, container, selector)
   local buffers = require("kubectl.actions.buffers")
   local commands = require("kubectl.actions.commands")
 
@@ -120,12 +237,17 @@ function M.LogsWithPods(pods, display_name, container)
     syntax = "k8s_pod_logs",
     hints = {
       { key = "<Plug>(kubectl.follow)", desc = "Follow" },
-      { key = "<Plug>(kubectl.history)", desc = "History [" .. tostring(opts.since) .. "]" },
+      { key = "<Plug>(kubectl.history)", desc = "History [" .. (selector and "runs" or tostring(opts.since)) .. "]" },
+      { key = "<Plug>(kubectl.tail_logs)", desc = "Tail[" .. ((opts.tail or 0) > 0 and opts.tail or "all") .. "]" },
       { key = "<Plug>(kubectl.prefix)", desc = "Prefix[" .. tostring(opts.prefix) .. "]" },
       { key = "<Plug>(kubectl.timestamps)", desc = "Timestamps[" .. tostring(opts.timestamps) .. "]" },
       { key = "<Plug>(kubectl.wrap)", desc = "Wrap" },
       { key = "<Plug>(kubectl.previous_logs)", desc = "Previous[" .. tostring(opts.previous) .. "]" },
+      { key = "<Plug>(kubectl.ordered_logs)", desc = "Ordered[" .. tostring(opts.ordered) .. "]" },
+      { key = "<Plug>(kubectl.crash_logs)", desc = "Crash[" .. tostring(opts.crash or false) .. "]" },
+      { key = "<Plug>(kubectl.histogram_split)", desc = "Histogram[" .. (opts.histogram_split or "total") .. "]" },
       { key = "<Plug>(kubectl.expand_json)", desc = "Toggle JSON" },
+      { key = "<Plug>(kubectl.export_logs)", desc = "Export" },
     },
     panes = {
       { title = "Logs" },
@@ -134,7 +256,7 @@ function M.LogsWithPods(pods, display_name, container)
 
   builder.view_framed(def, {
     recreate_func = M.LogsWithPods,
-    recreate_args = { pods, display_name, container },
+    recreate_args = { pods, display_name, container, selector },
   })
 
   -- Get actual window width after view is created, minus 2 for histogram borders
@@ -144,25 +266,44 @@ function M.LogsWithPods(pods, display_name, container)
   -- Store pods in buffer for option changes (gp, gt, gh, etc.)
   vim.api.nvim_buf_set_var(builder.buf_nr, "kubectl_log_pods", pods)
   vim.api.nvim_buf_set_var(builder.buf_nr, "kubectl_log_display", display_name)
+  vim.b[builder.buf_nr].kubectl_log_selector = selector
+
+  if opts.crash then
+    crash_logs(builder, pods, container, selector, opts)
+    return
+  end
 
   -- Fetch initial logs (returns JSON-encoded array of strings)
   commands.run_async("log_stream_async", {
     pods = pods,
+    selector = selector,
     container = container,
-    since = opts.since,
+    since = not selector and opts.since or nil,
+    tail_lines = (opts.tail or 0) > 0 and opts.tail or nil,
     previous = opts.previous,
     timestamps = opts.timestamps,
     prefix = opts.prefix,
+    include = opts.include,
+    exclude = opts.exclude,
+    level = opts.level,
+    field_filters = opts.field_filters,
+    ordered = opts.ordered,
     histogram_width = width,
+    histogram_split = opts.histogram_split,
+    histogram_data = true,
   }, function(result)
     if not result then
       return
     end
     builder.data = result
     builder.decodeJson()
+    local payload = builder.data or {}
+    builder.data = payload.lines or {}
+    M.log_histograms[builder.buf_nr] = payload.histogram
     vim.schedule(function()
       buffers.set_content(builder.buf_nr, {
         content = builder.data,
+        marks = histogram_marks(payload.histogram, builder.data),
         header = { data = {}, marks = {} },
       })
     end)
@@ -170,13 +311,24 @@ function M.LogsWithPods(pods, display_name, container)
 end
 
 function M.Logs()
-  local pods, display_name = get_pods_for_logs()
-  M.LogsWithPods(pods, display_name, M.selection.container)
+  local pods, display_name, selector = get_pods_for_logs()
+  M.LogsWithPods(pods, display_name, M.selection.container, selector)
+end
+
+--- Logs of a Job's pods or a CronJob's recent runs, prefixed by run. Runs are
+--- shown in full, `since` does not apply.
+---@param kind "Job"|"CronJob"
+---@param name string
+---@param ns string
+function M.JobLogs(kind, name, ns)
+  M.selection = {}
+  local selector = { namespace = ns, kind = kind, name = name, runs = config.options.logs.cronjob_runs }
+  M.LogsWithPods({}, string.lower(kind) .. "/" .. name .. " | " .. ns, nil, selector)
 end
 
 --- Toggle follow mode - stops current session or starts streaming from now
 function M.TailLogs()
-  local pods, display_name = get_pods_for_logs()
+  local pods, display_name, selector = get_pods_for_logs()
 
   local buf = vim.api.nvim_get_current_buf()
   local win = vim.api.nvim_get_current_win()
@@ -188,7 +340,7 @@ function M.TailLogs()
     return
   end
 
-  if #pods == 0 then
+  if #pods == 0 and not selector then
     vim.notify("No pods selected", vim.log.levels.WARN)
     return
   end
@@ -198,7 +350,7 @@ function M.TailLogs()
 
   -- Create and start new session
   local session = log_session.get_or_create(buf, win, opts)
-  local success = session:start(pods, M.selection.container)
+  local success = session:start(pods, M.selection.container, selector)
 
   if success then
     -- Move cursor to end
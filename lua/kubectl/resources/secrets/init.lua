@@ -19,7 +19,7 @@ local M = BaseResource.extend({
 })
 
 -- Override Yaml with hints for base64 decode
-function M.Yaml(name, ns)
+function M.Yaml(name, ns, context)
   local title = M.definition.resource .. " | " .. name .. " | " .. ns
 
   local def = {
@@ -43,9 +43,10 @@ function M.Yaml(name, ns)
       namespace = ns,
       name = name,
       output = "yaml",
+      context = context,
     },
     recreate_func = M.Yaml,
-    recreate_args = { name, ns },
+    recreate_args = { name, ns, context },
   })
 end
 
@@ -29,6 +29,8 @@ M.proxyUrl = ""
 M.column_visibility = {}
 ---@type table<string, string[]>
 M.column_order = {}
+---@type string[] Contexts whose rows are merged into one table; empty shows the current context only
+M.aggregate_contexts = {}
 
 ---------------------------------------------------------------------------
 -- Per-buffer state for split support
@@ -8,7 +8,7 @@ local M = {}
 local plug_mapping_cache = nil
 
 --- Headers that cannot be hidden (always visible)
-M.required_headers = { NAME = true, NAMESPACE = true }
+M.required_headers = { NAME = true, NAMESPACE = true, CONTEXT = true }
 
 --- Calculate column widths for table data
 ---@param rows table[]
@@ -334,7 +334,7 @@ function M.generateDividerRow(hints, marks)
   table.insert(hints, row)
 end
 
----@param divider { resource: string, count: string, filter: string }|nil
+---@param divider { resource: string, count: string, filter: string, status?: string }|nil
 ---@return string The formatted divider row
 function M.generateDividerWinbar(divider, win)
   win = win or vim.api.nvim_get_current_win()
@@ -350,6 +350,8 @@ function M.generateDividerWinbar(divider, win)
   local resource = divider.resource or ""
   local count = divider.count or ""
   local filter = divider.filter or ""
+  -- Watch errors can be long; keep them to one line and out of the statusline syntax
+  local status = vim.fn.strcharpart(((divider.status or ""):gsub("\n", " ")), 0, 80)
   local bufnr = vim.api.nvim_win_get_buf(win)
   local selected_count = vim.tbl_count(state.getSelections(bufnr))
 
@@ -372,6 +374,7 @@ function M.generateDividerWinbar(divider, win)
     "%#KubectlHeader#",
     "] ",
     filter ~= "" and ("</%#KubectlPending#" .. filter .. "%#KubectlHeader#> ") or "",
+    status ~= "" and ("%#KubectlError#" .. status:gsub("%%", "%%%%") .. "%#KubectlHeader# ") or "",
     "%*",
   })
 
@@ -379,6 +382,9 @@ function M.generateDividerWinbar(divider, win)
   if filter ~= "" then
     center_len = center_len + #filter + 4
   end
+  if status ~= "" then
+    center_len = center_len + #status + 1
+  end
 
   local total_pad = text_width - center_len
   local left_len = math.floor(total_pad / 2)
@@ -677,14 +683,15 @@ function M.getVisibleHeaders(resource, original_headers)
   return visible
 end
 
---- Get column indices for NAME and NAMESPACE based on visible headers
+--- Get column indices for NAME, NAMESPACE and CONTEXT based on visible headers
 ---@param resource string Resource name
----@param original_headers string[] Original headers from definition
+---@param original_headers string[] Headers the table was drawn with
 ---@return number|nil name_col Index of NAME column (1-based)
 ---@return number|nil ns_col Index of NAMESPACE column (1-based), nil if not visible
+---@return number|nil context_col Index of CONTEXT column (1-based), only set in aggregated tables
 function M.getColumnIndices(resource, original_headers)
   local visible = M.getVisibleHeaders(resource, original_headers)
-  return M.find_index(visible, "NAME"), M.find_index(visible, "NAMESPACE")
+  return M.find_index(visible, "NAME"), M.find_index(visible, "NAMESPACE"), M.find_index(visible, "CONTEXT")
 end
 
 --- Get the current selection from the buffer
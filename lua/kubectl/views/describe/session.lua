@@ -137,7 +137,8 @@ end
 ---@param name string Resource name
 ---@param namespace string|nil Namespace (nil for cluster-scoped)
 ---@param gvk table GVK {k, g, v}
-function M.view(resource, name, namespace, gvk)
+---@param context? string Context the resource lives in, defaults to the current one
+function M.view(resource, name, namespace, gvk, context)
   local display_ns = namespace and (" | " .. namespace) or ""
   local title = resource .. " | " .. name .. display_ns
 
@@ -155,7 +156,7 @@ function M.view(resource, name, namespace, gvk)
   local builder = manager.get_or_create(definition.resource)
   builder.view_framed(definition, {
     recreate_func = M.view,
-    recreate_args = { resource, name, namespace, gvk },
+    recreate_args = { resource, name, namespace, gvk, context },
   })
 
   -- Create and start session
@@ -164,7 +165,7 @@ function M.view(resource, name, namespace, gvk)
     return create_session(builder.buf_nr, builder.win_nr, {
       name = name,
       namespace = namespace or "",
-      context = state.context["current-context"],
+      context = context or state.context["current-context"],
       gvk = gvk,
     }, builder)
   end)
@@ -78,9 +78,9 @@ function M.View()
   local marks = {}
 
   local instructions = {
-    "Use commas to separate multiple patterns.",
-    "Prefix a pattern with ! for negative filtering.",
-    "All patterns must match for a line to be included.",
+    "Use commas to separate multiple patterns; all must match.",
+    "Prefix a pattern with ! for negative filtering, use | for alternatives.",
+    "Scope to a column with status:Run, status=Running, name~^api-, restarts>3, age<10m.",
   }
 
   for _, line in ipairs(instructions) do
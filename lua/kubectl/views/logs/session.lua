@@ -1,6 +1,7 @@
 --- LogSession manager
 --- Uses resource_manager for instance lifecycle, adds streaming-specific behavior
 local config = require("kubectl.config")
+local hl = require("kubectl.actions.highlight")
 local manager = require("kubectl.resource_manager")
 
 local M = {}
@@ -8,11 +9,23 @@ local M = {}
 -- Key prefix for log sessions in the manager
 local KEY_PREFIX = "log_session:"
 
+local ns_id = vim.api.nvim_create_namespace("__kubectl_log_matches")
+
 ---@class kubectl.LogSessionOptions
 ---@field since string Log history duration (e.g., "5m", "1h")
+---@field tail? integer Only the last N lines of each container; 0 = all
 ---@field prefix boolean Show container prefix
 ---@field timestamps boolean Show timestamps
 ---@field previous boolean Show previous container logs
+---@field histogram_split? "total"|"level"|"pod" Rows of the one-shot histogram
+---@field crash? boolean Show previous and current logs with the last termination (one-shot fetch)
+---@field ordered? boolean Merge pods by timestamp instead of arrival order (one-shot fetch)
+---@field include? string Only show lines matching this regex
+---@field exclude? string Hide lines matching this regex
+---@field level? string Minimum log level (trace, debug, info, warn, error, fatal)
+---@field structured? boolean Render JSON/logfmt lines as "timestamp LEVEL msg key=value"
+---@field fields? string[] Extra keys shown after the message
+---@field field_filters? table<string, string> Only show structured lines whose field matches the regex
 
 -- Global options persist across sessions
 local global_options = nil
@@ -22,10 +35,89 @@ local function get_default_options()
     since = config.options.logs.since,
     prefix = config.options.logs.prefix,
     timestamps = config.options.logs.timestamps,
+    structured = config.options.logs.structured,
+    fields = config.options.logs.fields,
     previous = false,
   }
 end
 
+local level_hl = {
+  trace = hl.symbols.gray,
+  debug = hl.symbols.debug,
+  info = hl.symbols.info,
+  warn = hl.symbols.warning,
+  error = hl.symbols.error,
+  fatal = hl.symbols.error,
+}
+
+--- Render a parsed record as "prefix timestamp LEVEL msg key=value ..."
+---@return string line
+---@return table[] marks { start_col, end_col, hl_group }
+local function render_record(item)
+  if not item.format then
+    return item.text, {}
+  end
+
+  local line = item.prefix
+  local marks = {}
+  if item.timestamp then
+    table.insert(marks, { #line, #line + #item.timestamp, hl.symbols.gray })
+    line = line .. item.timestamp .. " "
+  end
+  if item.level then
+    local level = string.upper(item.level)
+    table.insert(marks, { #line, #line + #level, level_hl[item.level] })
+    line = line .. string.format("%-5s ", level)
+  end
+  line = line .. (item.msg or "")
+
+  local keys = vim.tbl_keys(item.fields or {})
+  table.sort(keys)
+  for _, key in ipairs(keys) do
+    line = line .. "  "
+    table.insert(marks, { #line, #line + #key + 1, hl.symbols.gray })
+    line = line .. key .. "=" .. item.fields[key]
+  end
+  return (line:gsub("\n", "\\n")), marks
+end
+
+--- Read the next chunk from Rust
+---@return string[]? lines
+---@return table[] marks { row, start_col, end_col, hl_group } with rows relative to the chunk
+local function read_lines(session)
+  local lines, marks = {}, {}
+  if session.options.structured then
+    local records = session.rust_session:read_records()
+    if not records then
+      return nil, marks
+    end
+    for i, item in ipairs(records) do
+      local line, line_marks = render_record(item)
+      lines[i] = line
+      for _, mark in ipairs(line_marks) do
+        table.insert(marks, { i - 1, mark[1], mark[2], mark[3] })
+      end
+      if not item.format then
+        for _, span in ipairs(item.spans) do
+          table.insert(marks, { i - 1, span[1], span[2], hl.symbols.match })
+        end
+      end
+    end
+    return lines, marks
+  end
+
+  local chunk = session.rust_session:read_chunk_with_spans()
+  if not chunk then
+    return nil, marks
+  end
+  for i, spans in ipairs(chunk.spans) do
+    for _, span in ipairs(spans) do
+      table.insert(marks, { i - 1, span[1], span[2], hl.symbols.match })
+    end
+  end
+  return chunk.lines, marks
+end
+
 local function session_key(buf)
   return KEY_PREFIX .. buf
 end
@@ -89,8 +181,9 @@ local function create_session(buf, win, options)
   --- Start streaming logs for the given pods
   ---@param pods table[] Array of {name, namespace} tables
   ---@param container string? Container name
+  ---@param selector kubectl.LogSelector? Follow pods matching this instead, attaching new ones
   ---@return boolean success
-  function session:start(pods, container)
+  function session:start(pods, container, selector)
     if self.stopped then
       return false
     end
@@ -98,11 +191,19 @@ local function create_session(buf, win, options)
     local client = require("kubectl.client")
     local ok, sess = pcall(client.log_session, {
       pods = pods,
+      selector = selector,
       container = container,
       timestamps = self.options.timestamps,
       follow = true,
+      tail_lines = (self.options.tail or 0) > 0 and self.options.tail or nil,
       previous = false,
       prefix = self.options.prefix and true or nil,
+      include = self.options.include,
+      exclude = self.options.exclude,
+      level = self.options.level,
+      structured = self.options.structured,
+      fields = self.options.fields,
+      field_filters = self.options.field_filters,
     })
 
     if not ok or not sess then
@@ -111,6 +212,8 @@ local function create_session(buf, win, options)
     end
 
     self.rust_session = sess
+    -- Session line N lands on buffer line first_row + N
+    self.first_row = vim.api.nvim_buf_line_count(self.buf)
 
     local timer = vim.uv.new_timer()
     self.timer = timer
@@ -135,12 +238,16 @@ local function create_session(buf, win, options)
           return
         end
 
-        local read_ok, lines = pcall(function()
-          return this.rust_session:read_chunk()
-        end)
+        local read_ok, lines, marks = pcall(read_lines, this)
         if read_ok and lines and #lines > 0 then
           local start_line = vim.api.nvim_buf_line_count(this.buf)
           vim.api.nvim_buf_set_lines(this.buf, start_line, start_line, false, lines)
+          for _, mark in ipairs(marks) do
+            pcall(vim.api.nvim_buf_set_extmark, this.buf, ns_id, start_line + mark[1], mark[2], {
+              end_col = mark[3],
+              hl_group = mark[4],
+            })
+          end
           vim.api.nvim_set_option_value("modified", false, { buf = this.buf })
 
           if vim.api.nvim_win_is_valid(this.win) and this.win == vim.api.nvim_get_current_win() then
@@ -164,6 +271,30 @@ local function create_session(buf, win, options)
     return true
   end
 
+  --- Search the lines this session received on a worker thread
+  ---@param pattern string Regex (Rust syntax)
+  ---@param callback fun(result: kubectl.LogSearchResult?, err: string?)
+  function session:search(pattern, callback)
+    if not self.rust_session then
+      callback(nil, "No active log session")
+      return
+    end
+    local commands = require("kubectl.actions.commands")
+    local args = { session = self.rust_session:id(), pattern = pattern }
+    commands.run_async("log_search_async", args, function(result, err)
+      if not result then
+        callback(nil, err)
+        return
+      end
+      local ok, decoded = pcall(vim.json.decode, result)
+      if not ok then
+        callback(nil, decoded)
+        return
+      end
+      callback(decoded)
+    end)
+  end
+
   function session:get_options()
     return self.options
   end
@@ -229,6 +360,51 @@ function M.is_active(buf)
   return session ~= nil and session:is_active()
 end
 
+--- Search a followed log buffer and list the matching lines in the location list
+---@param buf integer Buffer number
+---@param pattern string Regex (Rust syntax)
+function M.search(buf, pattern)
+  local session = M.get(buf)
+  if not session or not session.rust_session then
+    vim.notify("Search needs a follow session, use / for fetched logs", vim.log.levels.WARN)
+    return
+  end
+
+  session:search(pattern, function(result, err)
+    vim.schedule(function()
+      if not result then
+        vim.notify("Log search failed: " .. tostring(err), vim.log.levels.ERROR)
+        return
+      end
+      if not vim.api.nvim_buf_is_valid(buf) or not vim.api.nvim_win_is_valid(session.win) then
+        return
+      end
+
+      local items = {}
+      for _, match in ipairs(result.matches) do
+        local lnum = session.first_row + match.line
+        local text = vim.api.nvim_buf_get_lines(buf, lnum - 1, lnum, false)[1] or ""
+        if match.count > 1 then
+          text = "(" .. match.count .. "x) " .. text
+        end
+        table.insert(items, { bufnr = buf, lnum = lnum, text = text })
+      end
+      vim.fn.setloclist(session.win, {}, " ", { title = "Log search: " .. pattern, items = items })
+
+      local msg = string.format("%d matches on %d lines", result.total, result.lines)
+      if #items < result.lines then
+        msg = msg .. string.format(" (listing first %d)", #items)
+      end
+      vim.notify(msg)
+      if #items > 0 then
+        vim.api.nvim_win_call(session.win, function()
+          vim.cmd("lopen")
+        end)
+      end
+    end)
+  end)
+end
+
 --- Get current global options
 ---@param _ integer? Buffer number (ignored, kept for API compatibility)
 ---@return kubectl.LogSessionOptions
@@ -4,6 +4,28 @@ local tables = require("kubectl.utils.tables")
 
 local M = {}
 
+local status_hl = {
+  active = hl.symbols.success,
+  reconnecting = hl.symbols.pending,
+  failed = hl.symbols.error,
+}
+
+--- Human readable byte count, e.g. 1.5K
+---@param bytes integer
+---@return string
+function M.format_bytes(bytes)
+  local units = { "B", "K", "M", "G", "T" }
+  local value, unit = bytes, 1
+  while value >= 1024 and unit < #units do
+    value = value / 1024
+    unit = unit + 1
+  end
+  if unit == 1 then
+    return string.format("%d%s", value, units[unit])
+  end
+  return string.format("%.1f%s", value, units[unit])
+end
+
 M.definition = {
   resource = "portforward",
   ft = "k8s_portforward",
@@ -11,6 +33,7 @@ M.definition = {
   hints = {
     { key = "<Plug>(kubectl.delete)", desc = "Delete PF" },
     { key = "<Plug>(kubectl.browse)", desc = "Open in browser" },
+    { key = "<Plug>(kubectl.select)", desc = "Connections" },
     { key = "<Plug>(kubectl.quit)", desc = "close" },
   },
   panes = {
@@ -42,7 +65,7 @@ function M.View()
     hl_group = hl.symbols.gray,
   })
 
-  local headers = { "ID", "TYPE", "NAME", "NS", "HOST", "PORT" }
+  local headers = { "ID", "TYPE", "NAME", "NS", "HOST", "PORT", "STATUS", "CONNS", "IN/OUT", "POD", "ERROR" }
   builder.prettyData, builder.extmarks = tables.pretty_print(builder.data, headers)
   builder.displayContent(builder.win_nr)
   builder.fitToContent(1)
@@ -56,6 +79,17 @@ function M.View()
   end, { buffer = builder.buf_nr, silent = true })
 end
 
+--- "local:remote" of each mapping, e.g. 8080:80,9090:9090
+---@param ports kubectl.ForwardedPort[]
+---@return string
+function M.format_ports(ports)
+  local out = {}
+  for _, port in ipairs(ports) do
+    table.insert(out, port.local_port .. ":" .. port.remote_port)
+  end
+  return table.concat(out, ",")
+end
+
 function M.getPFRows(type)
   local client = require("kubectl.client")
   local pfs = client.portforward_list()
@@ -63,11 +97,22 @@ function M.getPFRows(type)
   for _, value in pairs(pfs) do
     local item = {
       id = { value = value.id, symbol = hl.symbols.gray },
-      type = { value = value.type, symbol = hl.symbols.info },
+      type = { value = value.round_robin and (value.type .. " (rr)") or value.type, symbol = hl.symbols.info },
       name = { value = value.name, symbol = hl.symbols.success },
       ns = { value = value.namespace, symbol = hl.symbols.info },
       host = { value = value.host, symbol = hl.symbols.pending },
-      port = { value = value.local_port .. ":" .. value.remote_port, symbol = hl.symbols.pending },
+      port = { value = M.format_ports(value.ports), symbol = hl.symbols.pending },
+      status = { value = value.status, symbol = status_hl[value.status] },
+      conns = {
+        value = value.active_connections .. "/" .. value.total_connections,
+        symbol = value.active_connections > 0 and hl.symbols.success or hl.symbols.gray,
+      },
+      ["in/out"] = {
+        value = M.format_bytes(value.bytes_in) .. "/" .. M.format_bytes(value.bytes_out),
+        symbol = hl.symbols.info,
+      },
+      pod = { value = value.pod or "", symbol = hl.symbols.gray },
+      error = { value = value.error or "", symbol = hl.symbols.gray },
     }
     if not type then
       table.insert(data, item)
@@ -110,6 +155,52 @@ function M.getCurrentSelection()
   return tables.getCurrentSelection(5, 6)
 end
 
+--- Show the traffic of a port forward and its recent connections, newest first.
+---@param id integer
+function M.ShowConnections(id)
+  local client = require("kubectl.client")
+  local ok, pf = pcall(client.portforward_detail, id)
+  if not ok then
+    vim.notify("Failed to get port forward " .. id .. ": " .. tostring(pf), vim.log.levels.ERROR)
+    return
+  end
+
+  local lines = {
+    string.format(
+      "%s/%s %s %s: %d active, %d total, %s in, %s out, last activity %s",
+      pf.type,
+      pf.name,
+      pf.host,
+      M.format_ports(pf.ports),
+      pf.active_connections,
+      pf.total_connections,
+      M.format_bytes(pf.bytes_in),
+      M.format_bytes(pf.bytes_out),
+      pf.last_activity or "never"
+    ),
+  }
+  if #pf.connections == 0 then
+    table.insert(lines, "No finished connections")
+  end
+  for _, conn in ipairs(pf.connections) do
+    table.insert(
+      lines,
+      string.format(
+        "%s %s -> %s:%d %dms %s in %s out%s",
+        conn.started,
+        conn.peer,
+        conn.pod or "-",
+        conn.remote_port,
+        conn.duration_ms,
+        M.format_bytes(conn.bytes_in),
+        M.format_bytes(conn.bytes_out),
+        conn.error and (" error: " .. conn.error) or ""
+      )
+    )
+  end
+  vim.notify(table.concat(lines, "\n"), vim.log.levels.INFO)
+end
+
 function M.OpenBrowser(host, port)
   local proto = port == "443" and "https" or "http"
   local url
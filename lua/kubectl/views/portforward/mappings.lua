@@ -42,6 +42,18 @@ M.overrides = {
       end
     end,
   },
+  ["<Plug>(kubectl.select)"] = {
+    noremap = true,
+    silent = true,
+    desc = "Show connections",
+    callback = function()
+      local id = tables.getCurrentSelection(1)
+      if not id or id == "ID" then
+        return
+      end
+      view.ShowConnections(tonumber(id))
+    end,
+  },
 }
 
 M.register = function()
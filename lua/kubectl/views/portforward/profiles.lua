@@ -0,0 +1,268 @@
+local commands = require("kubectl.actions.commands")
+
+local M = {}
+
+local file_name = "portforward_profiles.json"
+
+--- Keys of forwards being started, so overlapping starts don't start them twice
+local starting = {}
+
+---@class kubectl.PortForwardProfileEntry
+---@field context string
+---@field namespace string
+---@field kind "pod"|"service"
+---@field name string
+---@field host string
+---@field round_robin? boolean
+---@field ports { local_port: integer, remote_port: integer|string }[]
+
+---@class kubectl.PortForwardProfile
+---@field autostart boolean Start the forwards when their context becomes active
+---@field forwards kubectl.PortForwardProfileEntry[]
+
+--- Saved profiles by name
+---@return table<string, kubectl.PortForwardProfile>
+function M.load()
+  return commands.read_file(file_name) or {}
+end
+
+---@param profiles table<string, kubectl.PortForwardProfile>
+local function store(profiles)
+  local ok, err = commands.save_file(file_name, profiles)
+  if not ok then
+    vim.notify("Failed to save port forward profiles: " .. err, vim.log.levels.ERROR)
+  end
+  return ok
+end
+
+--- Identifies a forward so a running one is not started twice
+---@param fwd kubectl.PortForwardProfileEntry
+---@return string
+local function key(fwd)
+  local ports = {}
+  for _, port in ipairs(fwd.ports) do
+    table.insert(ports, port.local_port .. ":" .. port.remote_port)
+  end
+  return table.concat({ fwd.context, fwd.namespace, fwd.kind, fwd.name, fwd.host, table.concat(ports, ",") }, "|")
+end
+
+--- Running forwards as profile entries; named remote ports keep their name
+---@return kubectl.PortForwardProfileEntry[]
+local function running()
+  local client = require("kubectl.client")
+  local entries = {}
+  for _, pf in pairs(client.portforward_list()) do
+    local ports = {}
+    for _, port in ipairs(pf.ports) do
+      table.insert(ports, { local_port = port.local_port, remote_port = port.name or port.remote_port })
+    end
+    table.insert(entries, {
+      context = pf.context,
+      namespace = pf.namespace,
+      kind = pf.type,
+      name = pf.name,
+      host = pf.host,
+      round_robin = pf.round_robin,
+      ports = ports,
+    })
+  end
+  table.sort(entries, function(a, b)
+    return key(a) < key(b)
+  end)
+  return entries
+end
+
+--- Names of the saved profiles, sorted
+---@return string[]
+function M.names()
+  local names = vim.tbl_keys(M.load())
+  table.sort(names)
+  return names
+end
+
+--- Save the running port forwards as a profile, replacing one with the same name
+---@param name string
+---@param autostart? boolean
+function M.save(name, autostart)
+  local forwards = running()
+  if #forwards == 0 then
+    vim.notify("No port forwards running", vim.log.levels.WARN)
+    return
+  end
+  local profiles = M.load()
+  profiles[name] = { autostart = autostart or false, forwards = forwards }
+  if store(profiles) then
+    vim.notify(string.format("Saved %d port forwards as profile %s", #forwards, name))
+  end
+end
+
+---@param name string
+function M.delete(name)
+  local profiles = M.load()
+  if not profiles[name] then
+    vim.notify("No port forward profile named " .. name, vim.log.levels.ERROR)
+    return
+  end
+  profiles[name] = nil
+  if store(profiles) then
+    vim.notify("Deleted port forward profile " .. name)
+  end
+end
+
+--- Restart the profile's forwards automatically when their context becomes active
+---@param name string
+---@param enabled boolean
+function M.set_autostart(name, enabled)
+  local profiles = M.load()
+  if not profiles[name] then
+    vim.notify("No port forward profile named " .. name, vim.log.levels.ERROR)
+    return
+  end
+  profiles[name].autostart = enabled
+  if store(profiles) then
+    vim.notify(string.format("Autostart %s for profile %s", enabled and "on" or "off", name))
+  end
+end
+
+--- Start the forwards of a profile that are not running yet, each in its own context,
+--- off the main thread; `on_done` runs once all of them have finished
+---@param profile kubectl.PortForwardProfile
+---@param context? string Only start the forwards of this context
+---@param on_done fun(started: integer, errors: string[])
+local function start_forwards(profile, context, on_done)
+  local active = {}
+  for _, fwd in ipairs(running()) do
+    active[key(fwd)] = true
+  end
+
+  local pending = {}
+  for _, fwd in ipairs(profile.forwards or {}) do
+    local k = key(fwd)
+    if (not context or fwd.context == context) and not active[k] and not starting[k] then
+      starting[k] = true
+      table.insert(pending, fwd)
+    end
+  end
+  if #pending == 0 then
+    on_done(0, {})
+    return
+  end
+
+  local batch = {}
+  for _, fwd in ipairs(pending) do
+    table.insert(batch, {
+      context = fwd.context,
+      kind = fwd.kind,
+      name = fwd.name,
+      namespace = fwd.namespace,
+      host = fwd.host,
+      ports = fwd.ports,
+      round_robin = fwd.round_robin or false,
+    })
+  end
+  commands.run_async("portforward_start_all", batch, function(result, err)
+    vim.schedule(function()
+      for _, fwd in ipairs(pending) do
+        starting[key(fwd)] = nil
+      end
+      local started, errors = 0, {}
+      local ok, results = pcall(vim.json.decode, result or "")
+      if err or not ok then
+        table.insert(errors, tostring(err or results))
+        on_done(started, errors)
+        return
+      end
+      for i, res in ipairs(results) do
+        local fwd = pending[i]
+        if res.error then
+          table.insert(errors, string.format("%s %s/%s: %s", fwd.context, fwd.kind, fwd.name, res.error))
+        else
+          started = started + 1
+        end
+      end
+      on_done(started, errors)
+    end)
+  end)
+end
+
+---@param name string
+---@param started integer
+---@param errors string[]
+local function report(name, started, errors)
+  local msg = string.format("Profile %s: started %d port forwards", name, started)
+  if #errors > 0 then
+    msg = msg .. "\n" .. table.concat(errors, "\n")
+  end
+  vim.notify(msg, #errors > 0 and vim.log.levels.WARN or vim.log.levels.INFO)
+end
+
+--- Start all of a profile's forwards, each in the context it was saved from
+---@param name string
+function M.start(name)
+  local profile = M.load()[name]
+  if not profile then
+    vim.notify("No port forward profile named " .. name, vim.log.levels.ERROR)
+    return
+  end
+  start_forwards(profile, nil, function(started, errors)
+    report(name, started, errors)
+  end)
+end
+
+--- Start the forwards of autostart profiles that belong to `context`
+---@param context string
+function M.restore(context)
+  for name, profile in pairs(M.load()) do
+    if profile.autostart then
+      start_forwards(profile, context, function(started, errors)
+        if started > 0 or #errors > 0 then
+          report(name, started, errors)
+        end
+      end)
+    end
+  end
+end
+
+--- Print the saved profiles and their forwards
+function M.list()
+  local profiles = M.load()
+  local lines = {}
+  for _, name in ipairs(M.names()) do
+    local profile = profiles[name]
+    table.insert(lines, name .. (profile.autostart and " (autostart)" or ""))
+    for _, fwd in ipairs(profile.forwards or {}) do
+      table.insert(lines, "  " .. key(fwd))
+    end
+  end
+  if #lines == 0 then
+    lines = { "No port forward profiles saved" }
+  end
+  vim.notify(table.concat(lines, "\n"))
+end
+
+M.actions = { "save", "start", "delete", "autostart", "list" }
+
+--- `:Kubectl pf-profile <action> [name] [on|off]`
+---@param args string[] Arguments after the subcommand
+function M.execute(args)
+  local action, name = args[1], args[2]
+  if action == "list" then
+    return M.list()
+  end
+  if not vim.tbl_contains(M.actions, action) or not name then
+    vim.notify("Usage: Kubectl pf-profile save|start|delete|autostart|list [name] [on|off]", vim.log.levels.ERROR)
+    return
+  end
+
+  if action == "save" then
+    M.save(name, args[3] == "autostart")
+  elseif action == "start" then
+    M.start(name)
+  elseif action == "delete" then
+    M.delete(name)
+  elseif action == "autostart" then
+    M.set_autostart(name, args[3] ~= "off")
+  end
+end
+
+return M